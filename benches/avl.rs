@@ -0,0 +1,46 @@
+//! Measures the time to insert keys into `AVL`.
+//!
+//! Run with `cargo bench --bench avl`. If insertion is O(log n), the time divided by
+//! n log2 n stays roughly constant as n doubles.
+
+mod common;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use algorithms_by_rust::tree::AVL;
+
+use common::shuffled_keys;
+
+fn measure(keys: &[u64]) -> Duration {
+    let start = Instant::now();
+    let mut avl = AVL::new();
+    for &k in keys {
+        avl.add(k);
+    }
+    let elapsed = start.elapsed();
+
+    black_box(&avl);
+    elapsed
+}
+
+fn main() {
+    println!("{:>10} {:>12} {:>12} {:>18}", "n", "order", "time (ms)", "ns / (n log2 n)");
+
+    for n in [125_000u64, 250_000, 500_000, 1_000_000] {
+        let sequential: Vec<u64> = (0..n).collect();
+        let shuffled = shuffled_keys(n);
+
+        for (order, keys) in [("sequential", &sequential), ("shuffled", &shuffled)] {
+            let elapsed = measure(keys);
+            let n_log_n = n as f64 * (n as f64).log2();
+            println!(
+                "{:>10} {:>12} {:>12.1} {:>18.3}",
+                n,
+                order,
+                elapsed.as_secs_f64() * 1e3,
+                elapsed.as_nanos() as f64 / n_log_n
+            );
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Helpers shared by the benchmarks.
+
+/// Generates a permutation of 0..n by a multiplicative step coprime to n.
+pub fn shuffled_keys(n: u64) -> Vec<u64> {
+    let step = (0..)
+        .map(|k| 2_654_435_761 + 2 * k)
+        .find(|&s| gcd(s, n) == 1)
+        .expect("There is a step coprime to n.");
+    (0..n).map(|i| i * step % n).collect()
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
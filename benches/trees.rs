@@ -0,0 +1,90 @@
+//! Compares `BinaryTree`, `AVL` and `RedBlackTree` on write-heavy workloads.
+//!
+//! Run with `cargo bench --bench trees`. For each tree, all keys are inserted and then
+//! removed in the same order, and the number of rotations is printed for the balanced
+//! trees. `BinaryTree` is skipped for sequential keys, where it degenerates into a list.
+
+mod common;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use algorithms_by_rust::tree::{BinaryTree, OrderedSet, RedBlackTree, AVL};
+
+use common::shuffled_keys;
+
+struct Measurement {
+    insert: Duration,
+    remove: Duration,
+    rotations: Option<usize>,
+}
+
+// Inserts and then removes the keys through `OrderedSet`, so that every tree runs the
+// same code. The rotations are read from the tree by `rotation_count` after removing.
+fn measure<S: OrderedSet<u64>>(
+    keys: &[u64],
+    rotation_count: fn(&S) -> Option<usize>,
+) -> Measurement {
+    let mut tree = S::default();
+
+    let start = Instant::now();
+    for &k in keys {
+        tree.insert(k);
+    }
+    let insert = start.elapsed();
+
+    let start = Instant::now();
+    for k in keys {
+        black_box(tree.remove(k));
+    }
+    let remove = start.elapsed();
+
+    Measurement {
+        insert,
+        remove,
+        rotations: rotation_count(&tree),
+    }
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>12} {:>16} {:>14} {:>14} {:>12}",
+        "n", "order", "tree", "insert (ms)", "remove (ms)", "rotations"
+    );
+
+    for n in [125_000u64, 250_000, 500_000, 1_000_000] {
+        let sequential: Vec<u64> = (0..n).collect();
+        let shuffled = shuffled_keys(n);
+
+        for (order, keys) in [("sequential", &sequential), ("shuffled", &shuffled)] {
+            let mut measurements = vec![
+                (
+                    "AVL",
+                    measure::<AVL<_>>(keys, |tree| Some(tree.rotation_count())),
+                ),
+                (
+                    "RedBlackTree",
+                    measure::<RedBlackTree<_>>(keys, |tree| Some(tree.rotation_count())),
+                ),
+            ];
+            if order == "shuffled" {
+                measurements.push(("BinaryTree", measure::<BinaryTree<_>>(keys, |_| None)));
+            }
+
+            for (tree, measurement) in measurements {
+                let rotations = measurement
+                    .rotations
+                    .map_or_else(|| String::from("-"), |r| r.to_string());
+                println!(
+                    "{:>10} {:>12} {:>16} {:>14.1} {:>14.1} {:>12}",
+                    n,
+                    order,
+                    tree,
+                    measurement.insert.as_secs_f64() * 1e3,
+                    measurement.remove.as_secs_f64() * 1e3,
+                    rotations
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
-mod stack;
-mod queue;
-mod binary_search;
+pub mod stack;
+pub mod queue;
+pub mod binary_search;
 
 pub use self::stack::Stack;
+pub use self::queue::Queue;
+pub use self::binary_search::binary_search;
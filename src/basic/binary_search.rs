@@ -10,7 +10,11 @@ fn is_sorted<T: Ord>(array: &[T]) -> bool {
     true
 }
 
-fn binary_search<T: Ord + Debug>(array: &[T], x: &T) -> Option<usize> {
+/// Searches a sorted slice for a given value.
+///
+/// If the value is found then Option::Some is returned, containing the index of a
+/// matching element. If the value is not found then Option::None is returned.
+pub fn binary_search<T: Ord + Debug>(array: &[T], x: &T) -> Option<usize> {
     assert!(is_sorted(array));
 
     if array.is_empty() {
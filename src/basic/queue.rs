@@ -1,48 +1,52 @@
-pub struct Queue<T> {
-    head: Option<Box<Node<T>>>,
-}
+use super::Stack;
 
-struct Node<T> {
-    value: T,
-    next: Option<Box<Self>>,
+// The front of the queue is the top of `outbox`, and `inbox` holds the rest in reverse.
+// If `outbox` is empty then `inbox` is also empty, so that `peek` needs no mutation.
+pub struct Queue<T> {
+    inbox: Stack<T>,
+    outbox: Stack<T>,
 }
 
-impl<T> Node<T> {
-    fn new(value: T) -> Self {
-        Self { value: value, next: None }
-    }
-
-    fn push(&mut self, value: T) {
-        match self.next {
-            Some(ref mut next) => { next.push(value) },
-            None => { self.next = Some(Box::new(Node::new(value))) },
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Self {
+            inbox: Stack::new(),
+            outbox: Stack::new(),
         }
     }
-}
 
-impl<T> Queue<T> {
-    pub fn new() -> Self {
-        Self { head: None }
+    pub fn is_empty(&self) -> bool {
+        self.outbox.is_empty()
     }
 
     pub fn peek(&self) -> Option<&T> {
-        self.head.as_ref().map(|h| &h.value)
+        self.outbox.peek()
     }
 
     pub fn push(&mut self, value: T) {
-        match self.head {
-            None => self.head = Some(Box::new(Node::new(value))),
-            Some(ref mut h) => h.push(value),
+        if self.outbox.is_empty() {
+            self.outbox.push(value);
+        } else {
+            self.inbox.push(value);
         }
     }
 
     pub fn pop(&mut self) -> Option<T> {
-        self.head.take()
-        .map(|ret| {
-            self.head = ret.next;
+        let value = self.outbox.pop();
+
+        if self.outbox.is_empty() {
+            while let Some(v) = self.inbox.pop() {
+                self.outbox.push(v);
+            }
+        }
+
+        value
+    }
+}
 
-            ret.value
-        })
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -67,4 +71,28 @@ mod tests {
         assert_eq!(que.pop(), None);
         assert_eq!(que.pop(), None);
     }
+
+    #[test]
+    fn test_queue_interleaved() {
+        let mut que = Queue::new();
+
+        que.push(1);
+        que.push(2);
+        assert_eq!(que.pop(), Some(1));
+        que.push(3);
+        assert_eq!(que.peek(), Some(&2));
+        assert_eq!(que.pop(), Some(2));
+        assert_eq!(que.pop(), Some(3));
+        assert!(que.is_empty());
+    }
+
+    #[test]
+    fn test_queue_many_values() {
+        let mut que = Queue::new();
+
+        for i in 0..1_000_000 {
+            que.push(i);
+        }
+        assert_eq!(que.pop(), Some(0));
+    }
 }
@@ -18,10 +18,18 @@ impl<T> Stack<T> {
         Self{cur: None}
     }
     
+    pub fn is_empty(&self) -> bool {
+        self.cur.is_none()
+    }
+
     pub fn peek(&self) -> Option<&T> {
         self.cur.as_ref().map(|rc| &rc.value)
     }
 
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.cur.as_mut().map(|rc| &mut rc.value)
+    }
+
     pub fn push(&mut self, value: T) {
         self.cur = Some(Box::new(Node::new(value, self.cur.take())));
     }
@@ -35,6 +43,23 @@ impl<T> Stack<T> {
     }
 }
 
+// Drops the nodes one by one, because the default recursive drop overflows the stack
+// for a long list.
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut cur = self.cur.take();
+        while let Some(mut node) = cur {
+            cur = node.prev.take();
+        }
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Stack;
@@ -54,4 +79,30 @@ mod tests {
         assert_eq!(stack.pop(), None);
         assert_eq!(stack.pop(), None);
     }
+
+    #[test]
+    fn test_peek_mut() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.peek_mut(), None);
+        assert!(stack.is_empty());
+
+        stack.push(5);
+        stack.push(9);
+        if let Some(top) = stack.peek_mut() {
+            *top = 8;
+        }
+
+        assert_eq!(stack.peek(), Some(&8));
+        assert_eq!(stack.pop(), Some(8));
+        assert_eq!(stack.pop(), Some(5));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_drop_many_values() {
+        let mut stack = Stack::new();
+        for i in 0..1_000_000 {
+            stack.push(i);
+        }
+    }
 }
\ No newline at end of file
@@ -1,2 +1,30 @@
+pub mod adjacency_list;
+pub mod weight;
+pub mod shortest_path_tree;
+pub mod dijkstra;
+pub mod bellman_ford;
+pub mod distance_matrix;
+pub mod floyd_warshall;
+pub mod johnson;
+pub mod bfs;
+pub mod dfs;
+pub mod scc;
+pub mod two_sat;
+pub mod minimum_spanning_tree;
 
-mod dijkstra;
+pub use adjacency_list::{Edge, Graph};
+pub use weight::{OrderedFloat, Overflow, Weight};
+pub use shortest_path_tree::ShortestPathTree;
+pub use dijkstra::dijkstra;
+pub use bellman_ford::{bellman_ford, BellmanFord, Distance};
+pub use distance_matrix::DistanceMatrix;
+pub use floyd_warshall::floyd_warshall;
+pub use johnson::{johnson, JohnsonError};
+pub use bfs::{bfs, zero_one_bfs};
+pub use dfs::{
+    dfs, dfs_forest, dfs_forest_from, find_cycle, topological_sort, Cycle, DepthFirstSearch,
+    EdgeKind,
+};
+pub use scc::{strongly_connected_components, StronglyConnectedComponents};
+pub use two_sat::TwoSat;
+pub use minimum_spanning_tree::{kruskal, prim, SpanningForest};
@@ -0,0 +1,227 @@
+use std::slice;
+
+/// An edge of `Graph<W>` going from `from` to `to` with a weight of type `W`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+}
+
+/// A graph represented by adjacency lists, generic over the edge weight `W`.
+///
+/// Vertices are identified by the indices `0..node_count()`. Every method that takes a
+/// vertex panics if the vertex is out of range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<W> {
+    adjacency: Vec<Vec<Edge<W>>>,
+    edge_count: usize,
+    directed: bool,
+}
+
+impl<W> Edge<W> {
+    /// Constructs a new edge.
+    pub fn new(from: usize, to: usize, weight: W) -> Self {
+        Self { from, to, weight }
+    }
+}
+
+impl<W> Graph<W> {
+    /// Constructs a new directed graph with `node_count` vertices and no edges.
+    pub fn new_directed(node_count: usize) -> Self {
+        Self::with_direction(node_count, true)
+    }
+
+    /// Constructs a new undirected graph with `node_count` vertices and no edges.
+    pub fn new_undirected(node_count: usize) -> Self {
+        Self::with_direction(node_count, false)
+    }
+
+    fn with_direction(node_count: usize, directed: bool) -> Self {
+        Self {
+            adjacency: (0..node_count).map(|_| Vec::new()).collect(),
+            edge_count: 0,
+            directed,
+        }
+    }
+
+    /// Creates a directed graph from a list of `(from, to, weight)` triples.
+    pub fn from_directed_edges<I>(node_count: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        W: Clone,
+    {
+        let mut graph = Self::new_directed(node_count);
+        graph.extend_edges(edges);
+        graph
+    }
+
+    /// Creates an undirected graph from a list of `(u, v, weight)` triples.
+    pub fn from_undirected_edges<I>(node_count: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        W: Clone,
+    {
+        let mut graph = Self::new_undirected(node_count);
+        graph.extend_edges(edges);
+        graph
+    }
+
+    /// Returns true if the graph is directed.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the number of vertices.
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Returns the number of edges.
+    ///
+    /// An undirected edge is counted once even though it can be traversed both ways.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Adds a new vertex without edges and returns its index.
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    /// Adds an edge from `from` to `to`.
+    ///
+    /// If the graph is undirected, the edge can also be traversed from `to` to `from`.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W)
+    where
+        W: Clone,
+    {
+        assert!(to < self.node_count(), "The vertex {} is out of range.", to);
+
+        if !self.directed && from != to {
+            self.adjacency[to].push(Edge::new(to, from, weight.clone()));
+        }
+        self.adjacency[from].push(Edge::new(from, to, weight));
+        self.edge_count += 1;
+    }
+
+    /// Adds every `(from, to, weight)` triple as an edge.
+    pub fn extend_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        W: Clone,
+    {
+        for (from, to, weight) in edges {
+            self.add_edge(from, to, weight);
+        }
+    }
+
+    /// Returns an iterator over the edges leaving `v`.
+    pub fn edges_from(&self, v: usize) -> slice::Iter<'_, Edge<W>> {
+        self.adjacency[v].iter()
+    }
+
+    /// Returns an iterator over the vertices adjacent to `v`.
+    pub fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges_from(v).map(|e| e.to)
+    }
+
+    /// Returns the number of edges leaving `v`.
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.adjacency[v].len()
+    }
+
+    /// Returns an iterator over all edges of the graph.
+    ///
+    /// An undirected edge is yielded once, oriented so that `from <= to`.
+    pub fn edges(&self) -> impl Iterator<Item = &Edge<W>> + '_ {
+        let directed = self.directed;
+        self.adjacency
+            .iter()
+            .flatten()
+            .filter(move |e| directed || e.from <= e.to)
+    }
+
+    /// Returns a directed graph whose edges are those of `self` reversed.
+    ///
+    /// The reverse of an undirected graph is an equal graph.
+    pub fn reversed(&self) -> Self
+    where
+        W: Clone,
+    {
+        if !self.directed {
+            return self.clone();
+        }
+
+        Self::from_directed_edges(
+            self.node_count(),
+            self.edges().map(|e| (e.to, e.from, e.weight.clone())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_graph() {
+        let g = Graph::from_directed_edges(4, vec![(0, 1, 5), (0, 2, 3), (2, 1, 1), (3, 3, 7)]);
+
+        assert!(g.is_directed());
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.neighbors(1).count(), 0);
+        assert_eq!(g.out_degree(3), 1);
+        assert_eq!(g.edges().count(), 4);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        let g = Graph::from_undirected_edges(3, vec![(0, 1, 'a'), (2, 1, 'b'), (2, 2, 'c')]);
+
+        assert!(!g.is_directed());
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(g.neighbors(2).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            g.edges().collect::<Vec<_>>(),
+            vec![
+                &Edge::new(0, 1, 'a'),
+                &Edge::new(1, 2, 'b'),
+                &Edge::new(2, 2, 'c')
+            ]
+        );
+    }
+
+    #[test]
+    fn add_node_and_edge() {
+        let mut g = Graph::new_directed(0);
+
+        let u = g.add_node();
+        let v = g.add_node();
+        g.add_edge(u, v, 1.5);
+
+        assert_eq!((u, v), (0, 1));
+        assert_eq!(g.edges_from(u).next(), Some(&Edge::new(0, 1, 1.5)));
+    }
+
+    #[test]
+    fn reversed_graph() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 1), (1, 2, 2)]);
+        let r = g.reversed();
+
+        assert_eq!(r.neighbors(2).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(r.neighbors(1).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(r.neighbors(0).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_edge_out_of_range() {
+        let mut g = Graph::new_directed(2);
+        g.add_edge(0, 2, ());
+    }
+}
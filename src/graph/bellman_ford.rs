@@ -0,0 +1,353 @@
+use super::{Graph, Overflow, ShortestPathTree, Weight};
+
+/// The distance between two vertices of a graph which may have negative cycles.
+///
+/// Distances are ordered as `NegativeInfinity < Finite(_) < Unreachable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Distance<W> {
+    /// The path passes through a negative cycle, so that there is no shortest path.
+    NegativeInfinity,
+    Finite(W),
+    Unreachable,
+}
+
+/// The result of `bellman_ford`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BellmanFord<W> {
+    source: usize,
+    distances: Vec<Distance<W>>,
+    parents: Vec<Option<usize>>,
+    negative_cycle: Option<Vec<usize>>,
+}
+
+/// Computes the shortest paths from `s` to every vertex, allowing negative edge weights.
+///
+/// The vertices reachable from a negative cycle get `Distance::NegativeInfinity`, and one
+/// of those cycles is reported by `BellmanFord::negative_cycle`. If the length of a path
+/// cannot be represented by `W` then Result::Err is returned.
+pub fn bellman_ford<W: Weight>(g: &Graph<W>, s: usize) -> Result<BellmanFord<W>, Overflow> {
+    let n = g.node_count();
+    let mut dis: Vec<Option<W>> = vec![None; n];
+    let mut parents = vec![None; n];
+    let mut overflowed = vec![false; n];
+    let mut underflowed = vec![false; n];
+
+    // Relaxes every edge once. Returns the vertices whose distances were updated, and the
+    // edges whose candidates were shorter than any representable distance.
+    let mut relax = |dis: &mut Vec<Option<W>>, parents: &mut Vec<Option<usize>>| {
+        let mut updated = Vec::new();
+        let mut underflows = Vec::new();
+        for e in (0..n).flat_map(|u| g.edges_from(u)) {
+            let Some(d) = dis[e.from] else {
+                continue;
+            };
+
+            // An overflowing candidate is longer than any representable distance, and an
+            // underflowing one is shorter.
+            let Some(nd) = d.checked_add(e.weight) else {
+                if e.weight.is_negative() {
+                    underflowed[e.to] = true;
+                    underflows.push((e.from, e.to));
+                } else {
+                    overflowed[e.to] = true;
+                }
+                continue;
+            };
+            if dis[e.to].is_none_or(|dt| dt > nd) {
+                dis[e.to] = Some(nd);
+                parents[e.to] = Some(e.from);
+                updated.push(e.to);
+            }
+        }
+
+        (updated, underflows)
+    };
+
+    dis[s] = Some(W::zero());
+    for _ in 1..n {
+        if relax(&mut dis, &mut parents).0.is_empty() {
+            break;
+        }
+    }
+
+    // A vertex which can still be relaxed is reachable from a negative cycle. So is the
+    // head of an underflowing edge if it is an ancestor of the tail, since the edge closes
+    // a cycle of the parents shorter than the difference of their distances.
+    let (mut updated, underflows) = relax(&mut dis, &mut parents);
+    for (u, v) in underflows {
+        if is_ancestor(&parents, v, u) {
+            parents[v] = Some(u);
+            updated.push(v);
+        }
+    }
+
+    let negative_cycle = updated.first().map(|&v| find_cycle(&parents, v));
+    let mut distances: Vec<Distance<W>> = dis
+        .iter()
+        .map(|d| d.map_or(Distance::Unreachable, Distance::Finite))
+        .collect();
+
+    let mut stack = updated;
+    while let Some(v) = stack.pop() {
+        if distances[v] == Distance::NegativeInfinity {
+            continue;
+        }
+
+        distances[v] = Distance::NegativeInfinity;
+        parents[v] = None;
+        stack.extend(g.neighbors(v));
+    }
+
+    // The distance of any other vertex with an overflowing candidate is not representable.
+    if (0..n).any(|v| {
+        (overflowed[v] && distances[v] == Distance::Unreachable)
+            || (underflowed[v] && distances[v] != Distance::NegativeInfinity)
+    }) {
+        return Err(Overflow);
+    }
+
+    Ok(BellmanFord {
+        source: s,
+        distances,
+        parents,
+        negative_cycle,
+    })
+}
+
+// Returns true if `ancestor` is `v` or is reached from `v` by following the parents.
+fn is_ancestor(parents: &[Option<usize>], ancestor: usize, v: usize) -> bool {
+    let mut v = v;
+    for _ in 0..parents.len() {
+        if v == ancestor {
+            return true;
+        }
+        let Some(p) = parents[v] else {
+            return false;
+        };
+        v = p;
+    }
+
+    false
+}
+
+// Follows the parents from a vertex updated in the n-th round, which is reachable from a
+// cycle of the parents. Every cycle of the parents is negative.
+fn find_cycle(parents: &[Option<usize>], v: usize) -> Vec<usize> {
+    let parent = |v: usize| parents[v].expect("A relaxed vertex must have a parent.");
+
+    let mut v = v;
+    for _ in 0..parents.len() {
+        v = parent(v);
+    }
+
+    let mut cycle = vec![v];
+    let mut u = parent(v);
+    while u != v {
+        cycle.push(u);
+        u = parent(u);
+    }
+    cycle.reverse();
+
+    cycle
+}
+
+impl<W: Weight> BellmanFord<W> {
+    /// Returns the source vertex of the search.
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Returns the distance from the source to `v`.
+    pub fn distance(&self, v: usize) -> Distance<W> {
+        self.distances[v]
+    }
+
+    /// Returns the distances from the source to all vertices.
+    pub fn distances(&self) -> &[Distance<W>] {
+        &self.distances
+    }
+
+    /// Returns true if a negative cycle is reachable from the source.
+    pub fn has_negative_cycle(&self) -> bool {
+        self.negative_cycle.is_some()
+    }
+
+    /// Returns a negative cycle reachable from the source.
+    ///
+    /// The cycle is given as vertices `[v0, v1, ..., vk]` such that there are edges
+    /// `v0 -> v1`, ..., `vk -> v0`.
+    pub fn negative_cycle(&self) -> Option<&[usize]> {
+        self.negative_cycle.as_deref()
+    }
+
+    /// Returns the vertices on a shortest path from the source to `target`, both ends included.
+    ///
+    /// If `target` is unreachable or its distance is negative infinity then Option::None is
+    /// returned.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        let Distance::Finite(_) = self.distances[target] else {
+            return None;
+        };
+
+        let mut path = vec![target];
+        let mut v = target;
+        while let Some(p) = self.parents[v] {
+            path.push(p);
+            v = p;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Converts the result into a `ShortestPathTree`.
+    ///
+    /// If a negative cycle is reachable from the source then Option::None is returned.
+    pub fn into_tree(self) -> Option<ShortestPathTree<W>> {
+        if self.has_negative_cycle() {
+            return None;
+        }
+
+        let distances = self
+            .distances
+            .into_iter()
+            .map(|d| match d {
+                Distance::Finite(d) => Some(d),
+                _ => None,
+            })
+            .collect();
+
+        Some(ShortestPathTree::new(self.source, distances, self.parents))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle_weight(g: &Graph<i64>, cycle: &[usize]) -> i64 {
+        (0..cycle.len())
+            .map(|i| {
+                let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                g.edges_from(u)
+                    .filter(|e| e.to == v)
+                    .map(|e| e.weight)
+                    .min()
+                    .expect("The cycle must consist of edges of the graph.")
+            })
+            .sum()
+    }
+
+    #[test]
+    fn negative_edges_without_cycle() {
+        let g = Graph::from_directed_edges(
+            5,
+            vec![(0, 1, 4), (0, 2, 5), (1, 3, -3), (2, 1, -2), (3, 2, 6)],
+        );
+
+        let result = bellman_ford(&g, 0).unwrap();
+        assert!(!result.has_negative_cycle());
+        assert_eq!(
+            result.distances(),
+            &[
+                Distance::Finite(0),
+                Distance::Finite(3),
+                Distance::Finite(5),
+                Distance::Finite(0),
+                Distance::Unreachable,
+            ]
+        );
+        assert_eq!(result.path_to(3), Some(vec![0, 2, 1, 3]));
+
+        let tree = result.into_tree().unwrap();
+        assert_eq!(tree.distance(3), Some(0));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1 is a negative cycle, and 4 is reachable from it.
+        let g = Graph::from_directed_edges(
+            6,
+            vec![
+                (0, 1, 1),
+                (1, 2, 2),
+                (2, 3, -4),
+                (3, 1, 1),
+                (3, 4, 1),
+                (0, 5, 3),
+                (5, 0, 1),
+            ],
+        );
+
+        let result = bellman_ford(&g, 0).unwrap();
+        assert!(result.has_negative_cycle());
+        assert_eq!(result.distance(0), Distance::Finite(0));
+        assert_eq!(result.distance(5), Distance::Finite(3));
+        for v in 1..5 {
+            assert_eq!(result.distance(v), Distance::NegativeInfinity);
+            assert_eq!(result.path_to(v), None);
+        }
+
+        let cycle = result.negative_cycle().unwrap();
+        let mut sorted = cycle.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 3]);
+        assert!(cycle_weight(&g, cycle) < 0);
+        assert_eq!(result.into_tree(), None);
+    }
+
+    #[test]
+    fn unreachable_negative_cycle() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 1), (2, 2, -1)]);
+
+        let result = bellman_ford(&g, 0).unwrap();
+        assert!(!result.has_negative_cycle());
+        assert_eq!(result.distance(2), Distance::Unreachable);
+    }
+
+    #[test]
+    fn undirected_negative_edge() {
+        let g = Graph::from_undirected_edges(2, vec![(0, 1, -1)]);
+
+        let result = bellman_ford(&g, 0).unwrap();
+        assert_eq!(result.distance(1), Distance::NegativeInfinity);
+        assert_eq!(result.negative_cycle().map(|c| c.len()), Some(2));
+    }
+
+    #[test]
+    fn narrow_negative_cycle() {
+        // The cycle 1 -> 2 -> 1 lowers the distances until they underflow `i8`.
+        let g =
+            Graph::from_directed_edges(4, vec![(0, 1, 1i8), (1, 2, -100), (2, 1, -100), (2, 3, 1)]);
+
+        let result = bellman_ford(&g, 0).unwrap();
+        assert_eq!(result.distance(0), Distance::Finite(0));
+        for v in 1..4 {
+            assert_eq!(result.distance(v), Distance::NegativeInfinity);
+        }
+        let mut cycle = result.negative_cycle().unwrap().to_vec();
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2]);
+
+        // Many rounds are needed before the slowly decreasing distances underflow.
+        let mut edges = vec![(0, 1, 0i8), (1, 2, -1), (2, 1, 0)];
+        edges.extend((3..300).map(|v| (v - 1, v, 0)));
+        let result = bellman_ford(&Graph::from_directed_edges(300, edges), 0).unwrap();
+        assert_eq!(result.distance(2), Distance::NegativeInfinity);
+        assert_eq!(result.negative_cycle().map(|c| c.len()), Some(2));
+    }
+
+    #[test]
+    fn overflow() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 100i8), (1, 2, 100)]);
+        assert_eq!(bellman_ford(&g, 0), Err(Overflow));
+        let g = Graph::from_directed_edges(3, vec![(0, 1, -100i8), (1, 2, -100)]);
+        assert_eq!(bellman_ford(&g, 0), Err(Overflow));
+
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 100i8), (1, 2, 100), (0, 2, 1)]);
+        assert_eq!(
+            bellman_ford(&g, 0).unwrap().distance(2),
+            Distance::Finite(1)
+        );
+    }
+}
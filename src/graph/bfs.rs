@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+
+use super::{Graph, Overflow, ShortestPathTree, Weight};
+use crate::basic::Queue;
+
+/// Computes the shortest paths from `s` to every vertex, counting the number of edges.
+///
+/// The weights of the edges are ignored.
+pub fn bfs<W>(g: &Graph<W>, s: usize) -> ShortestPathTree<usize> {
+    let mut dis = vec![None; g.node_count()];
+    let mut parents = vec![None; g.node_count()];
+    let mut que = Queue::new();
+
+    dis[s] = Some(0);
+    que.push((s, 0));
+    while let Some((v, d)) = que.pop() {
+        for u in g.neighbors(v) {
+            if dis[u].is_none() {
+                dis[u] = Some(d + 1);
+                parents[u] = Some(v);
+                que.push((u, d + 1));
+            }
+        }
+    }
+
+    ShortestPathTree::new(s, dis, parents)
+}
+
+/// Computes the shortest paths from `s` to every vertex of a graph whose weights are zero
+/// or a single positive value.
+///
+/// The edges of weight zero are pushed to the front of a deque, so that it runs in
+/// O(V + E). If the length of a shortest path to some vertex cannot be represented by `W`
+/// then Result::Err is returned.
+pub fn zero_one_bfs<W: Weight>(g: &Graph<W>, s: usize) -> Result<ShortestPathTree<W>, Overflow> {
+    let mut dis: Vec<Option<W>> = vec![None; g.node_count()];
+    let mut parents = vec![None; g.node_count()];
+    let mut overflowed = vec![false; g.node_count()];
+    let mut one = None;
+    let mut deque = VecDeque::new();
+
+    dis[s] = Some(W::zero());
+    deque.push_back((W::zero(), s));
+    while let Some((d, v)) = deque.pop_front() {
+        if dis[v].is_some_and(|dv| dv < d) {
+            continue;
+        }
+
+        for e in g.edges_from(v) {
+            // A weight equal in value to zero, such as negative zero, is zero.
+            let is_zero = e.weight.is_zero();
+            if !is_zero {
+                assert!(
+                    !e.weight.is_negative() && *one.get_or_insert(e.weight) == e.weight,
+                    "The weights are required to be zero or a single positive value."
+                );
+            }
+
+            // An overflowing candidate is longer than any representable distance.
+            let Some(nd) = d.checked_add(e.weight) else {
+                overflowed[e.to] = true;
+                continue;
+            };
+            if dis[e.to].is_none_or(|dt| dt > nd) {
+                dis[e.to] = Some(nd);
+                parents[e.to] = Some(v);
+                if is_zero {
+                    deque.push_front((nd, e.to));
+                } else {
+                    deque.push_back((nd, e.to));
+                }
+            }
+        }
+    }
+
+    if (0..g.node_count()).any(|v| overflowed[v] && dis[v].is_none()) {
+        return Err(Overflow);
+    }
+
+    Ok(ShortestPathTree::new(s, dis, parents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{dijkstra, OrderedFloat};
+
+    #[test]
+    fn bfs_distances() {
+        // 0 - 1 - 2 - 3
+        //  \     /
+        //   4 --
+        let g = Graph::from_undirected_edges(
+            6,
+            vec![(0, 1, ()), (1, 2, ()), (2, 3, ()), (0, 4, ()), (4, 2, ())],
+        );
+
+        let tree = bfs(&g, 0);
+        assert_eq!(
+            tree.distances(),
+            &[Some(0), Some(1), Some(2), Some(3), Some(1), None]
+        );
+        assert_eq!(tree.path_to(3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(tree.path_to(5), None);
+    }
+
+    #[test]
+    fn bfs_ignores_weights() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 1), (1, 2, 1), (0, 2, 100)]);
+
+        assert_eq!(bfs(&g, 0).path_to(2), Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn zero_one_bfs_distances() {
+        let g = Graph::from_directed_edges(
+            6,
+            vec![
+                (0, 1, 1u32),
+                (0, 2, 0),
+                (2, 3, 1),
+                (3, 1, 0),
+                (1, 4, 0),
+                (4, 5, 1),
+                (2, 5, 1),
+            ],
+        );
+
+        let tree = zero_one_bfs(&g, 0).unwrap();
+        assert_eq!(tree.distances(), dijkstra(&g, 0).unwrap().distances());
+        assert_eq!(tree.distance(4), Some(1));
+        assert_eq!(tree.path_to(5), Some(vec![0, 2, 5]));
+    }
+
+    #[test]
+    fn zero_one_bfs_overflow() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 200u8), (1, 2, 200)]);
+
+        assert_eq!(zero_one_bfs(&g, 0), Err(Overflow));
+    }
+
+    #[test]
+    fn zero_one_bfs_negative_zero_weight() {
+        let g = Graph::from_directed_edges(
+            3,
+            vec![
+                (0, 1, OrderedFloat(-0.0)),
+                (1, 2, OrderedFloat(0.0)),
+                (0, 2, OrderedFloat(1.0)),
+            ],
+        );
+
+        let tree = zero_one_bfs(&g, 0).unwrap();
+        assert_eq!(tree.distance(2), Some(OrderedFloat(0.0)));
+        assert_eq!(tree.path_to(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_one_bfs_with_various_weights() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 1), (1, 2, 2)]);
+        let _ = zero_one_bfs(&g, 0);
+    }
+}
@@ -0,0 +1,332 @@
+use std::error::Error;
+use std::fmt;
+
+use super::Graph;
+use crate::basic::Stack;
+
+/// The kind of an edge `u -> v` in a depth-first search forest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// `v` was discovered through the edge.
+    Tree,
+    /// `v` is an ancestor of `u`, so that the edge closes a cycle.
+    Back,
+    /// `v` is a proper descendant of `u` but not discovered through the edge.
+    Forward,
+    /// `v` is neither an ancestor nor a descendant of `u`.
+    Cross,
+}
+
+/// The result of a depth-first search.
+///
+/// Discovery and finish times are taken from a single clock, so that `u` is an ancestor
+/// of `v` if and only if the interval of `v` is contained in that of `u`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthFirstSearch {
+    discovered: Vec<Option<usize>>,
+    finished: Vec<Option<usize>>,
+    parents: Vec<Option<usize>>,
+    pre_order: Vec<usize>,
+    post_order: Vec<usize>,
+    edge_kinds: Vec<Vec<EdgeKind>>,
+}
+
+/// The error returned by `topological_sort` when the graph is not a DAG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+/// Runs a depth-first search from `s`.
+///
+/// The search is iterative, so that it does not overflow the stack on deep graphs. The
+/// edges of a vertex are visited in the order of `Graph::edges_from`.
+pub fn dfs<W>(g: &Graph<W>, s: usize) -> DepthFirstSearch {
+    let mut search = DepthFirstSearch::new(g.node_count());
+    search.visit(g, s);
+    search
+}
+
+/// Runs depth-first searches from every unvisited vertex in increasing order, so that
+/// the whole graph is covered by the resulting forest.
+pub fn dfs_forest<W>(g: &Graph<W>) -> DepthFirstSearch {
+    dfs_forest_from(g, 0..g.node_count())
+}
+
+/// Runs depth-first searches from every root in the given order, skipping the roots
+/// already visited.
+pub fn dfs_forest_from<W, I>(g: &Graph<W>, roots: I) -> DepthFirstSearch
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut search = DepthFirstSearch::new(g.node_count());
+    for s in roots {
+        if !search.is_visited(s) {
+            search.visit(g, s);
+        }
+    }
+    search
+}
+
+/// Returns a cycle of the graph if it exists.
+///
+/// The cycle is given as vertices `[v0, v1, ..., vk]` such that there are edges
+/// `v0 -> v1`, ..., `vk -> v0`. An undirected edge is regarded as a cycle of length two.
+pub fn find_cycle<W>(g: &Graph<W>) -> Option<Vec<usize>> {
+    cycle_of_back_edge(g, &dfs_forest(g))
+}
+
+// Returns the cycle closed by the first back edge of a search of the whole graph.
+fn cycle_of_back_edge<W>(g: &Graph<W>, search: &DepthFirstSearch) -> Option<Vec<usize>> {
+    (0..g.node_count()).find_map(|v| {
+        let (e, _) = g
+            .edges_from(v)
+            .zip(search.edge_kinds(v))
+            .find(|&(_, &kind)| kind == EdgeKind::Back)?;
+
+        let mut cycle = vec![v];
+        let mut u = v;
+        while u != e.to {
+            u = search.parents[u].expect("The head of a back edge must be an ancestor.");
+            cycle.push(u);
+        }
+        cycle.reverse();
+
+        Some(cycle)
+    })
+}
+
+/// Sorts the vertices so that every edge goes from an earlier vertex to a later one.
+///
+/// If the graph has a cycle then Result::Err is returned, containing a cycle as in
+/// `find_cycle`.
+pub fn topological_sort<W>(g: &Graph<W>) -> Result<Vec<usize>, Cycle> {
+    let search = dfs_forest(g);
+    if let Some(cycle) = cycle_of_back_edge(g, &search) {
+        return Err(Cycle(cycle));
+    }
+
+    let mut order = search.post_order;
+    order.reverse();
+
+    Ok(order)
+}
+
+impl DepthFirstSearch {
+    fn new(node_count: usize) -> Self {
+        Self {
+            discovered: vec![None; node_count],
+            finished: vec![None; node_count],
+            parents: vec![None; node_count],
+            pre_order: Vec::with_capacity(node_count),
+            post_order: Vec::with_capacity(node_count),
+            edge_kinds: vec![Vec::new(); node_count],
+        }
+    }
+
+    fn clock(&self) -> usize {
+        self.pre_order.len() + self.post_order.len()
+    }
+
+    fn discover(&mut self, v: usize) {
+        self.discovered[v] = Some(self.clock());
+        self.pre_order.push(v);
+    }
+
+    fn finish(&mut self, v: usize) {
+        self.finished[v] = Some(self.clock());
+        self.post_order.push(v);
+    }
+
+    // Visits every vertex reachable from `s` which is not visited yet.
+    fn visit<W>(&mut self, g: &Graph<W>, s: usize) {
+        // Each element is a vertex and the index of the next edge to be examined.
+        let mut stack = Stack::new();
+
+        self.discover(s);
+        stack.push((s, 0));
+        while let Some(top) = stack.peek_mut() {
+            let (v, i) = *top;
+            let Some(e) = g.edges_from(v).nth(i) else {
+                stack.pop();
+                self.finish(v);
+                continue;
+            };
+            top.1 += 1;
+
+            let u = e.to;
+            let kind = match (self.discovered[u], self.finished[u]) {
+                (None, _) => EdgeKind::Tree,
+                (Some(_), None) => EdgeKind::Back,
+                (Some(du), Some(_)) if self.discovered[v] < Some(du) => EdgeKind::Forward,
+                (Some(_), Some(_)) => EdgeKind::Cross,
+            };
+            self.edge_kinds[v].push(kind);
+
+            if kind == EdgeKind::Tree {
+                self.parents[u] = Some(v);
+                self.discover(u);
+                stack.push((u, 0));
+            }
+        }
+    }
+
+    /// Returns true if `v` was visited.
+    pub fn is_visited(&self, v: usize) -> bool {
+        self.discovered[v].is_some()
+    }
+
+    /// Returns the time when `v` was discovered, or Option::None if it was not visited.
+    pub fn discovery_time(&self, v: usize) -> Option<usize> {
+        self.discovered[v]
+    }
+
+    /// Returns the time when all descendants of `v` were finished, or Option::None if it
+    /// was not visited.
+    pub fn finish_time(&self, v: usize) -> Option<usize> {
+        self.finished[v]
+    }
+
+    /// Returns the parent of `v` in the search forest.
+    ///
+    /// Option::None is returned for the roots and for unvisited vertices.
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    /// Returns the visited vertices in the order of discovery.
+    pub fn pre_order(&self) -> &[usize] {
+        &self.pre_order
+    }
+
+    /// Returns the visited vertices in the order of finish.
+    pub fn post_order(&self) -> &[usize] {
+        &self.post_order
+    }
+
+    /// Returns the kinds of the edges leaving `v`, in the order of `Graph::edges_from`.
+    ///
+    /// If `v` was not visited then the slice is empty. Each direction of an undirected
+    /// edge is classified separately.
+    pub fn edge_kinds(&self, v: usize) -> &[EdgeKind] {
+        &self.edge_kinds[v]
+    }
+
+    /// Returns true if `u` is an ancestor of `v` in the search forest.
+    ///
+    /// A vertex is an ancestor of itself.
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        match (self.discovered[u], self.finished[u], self.discovered[v]) {
+            (Some(du), Some(fu), Some(dv)) => du <= dv && dv < fu,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the graph has a cycle {:?}", self.0)
+    }
+}
+
+impl Error for Cycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_cycle<W>(g: &Graph<W>, cycle: &[usize]) -> bool {
+        (0..cycle.len()).all(|i| {
+            let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            g.neighbors(u).any(|w| w == v)
+        })
+    }
+
+    #[test]
+    fn timestamps() {
+        // 0 -> 1 -> 2
+        // |         ^
+        // +--> 3 ---+
+        let g = Graph::from_directed_edges(5, vec![(0, 1, ()), (1, 2, ()), (0, 3, ()), (3, 2, ())]);
+
+        let search = dfs(&g, 0);
+        assert_eq!(search.pre_order(), &[0, 1, 2, 3]);
+        assert_eq!(search.post_order(), &[2, 1, 3, 0]);
+        assert_eq!(search.discovery_time(0), Some(0));
+        assert_eq!(search.discovery_time(2), Some(2));
+        assert_eq!(search.finish_time(2), Some(3));
+        assert_eq!(search.finish_time(0), Some(7));
+        assert_eq!(search.discovery_time(4), None);
+        assert_eq!(search.parent(3), Some(0));
+        assert!(search.is_ancestor(0, 2));
+        assert!(!search.is_ancestor(3, 2));
+        assert!(!search.is_visited(4));
+    }
+
+    #[test]
+    fn edge_classification() {
+        let g = Graph::from_directed_edges(
+            4,
+            vec![(0, 1, ()), (1, 2, ()), (2, 0, ()), (0, 2, ()), (3, 1, ())],
+        );
+
+        let search = dfs_forest(&g);
+        assert_eq!(search.edge_kinds(0), &[EdgeKind::Tree, EdgeKind::Forward]);
+        assert_eq!(search.edge_kinds(1), &[EdgeKind::Tree]);
+        assert_eq!(search.edge_kinds(2), &[EdgeKind::Back]);
+        assert_eq!(search.edge_kinds(3), &[EdgeKind::Cross]);
+        assert_eq!(search.parent(3), None);
+    }
+
+    #[test]
+    fn deep_graph() {
+        let n = 1_000_000;
+        let g = Graph::from_directed_edges(n, (1..n).map(|v| (v - 1, v, ())));
+
+        let search = dfs(&g, 0);
+        assert_eq!(search.finish_time(0), Some(2 * n - 1));
+        assert_eq!(topological_sort(&g).unwrap().len(), n);
+    }
+
+    #[test]
+    fn topological_order() {
+        let g = Graph::from_directed_edges(
+            6,
+            vec![
+                (5, 2, ()),
+                (5, 0, ()),
+                (4, 0, ()),
+                (4, 1, ()),
+                (2, 3, ()),
+                (3, 1, ()),
+            ],
+        );
+
+        let order = topological_sort(&g).unwrap();
+        let mut position = [0; 6];
+        for (i, &v) in order.iter().enumerate() {
+            position[v] = i;
+        }
+        assert!(g.edges().all(|e| position[e.from] < position[e.to]));
+        assert_eq!(find_cycle(&g), None);
+    }
+
+    #[test]
+    fn cycle_witness() {
+        let g = Graph::from_directed_edges(
+            5,
+            vec![(0, 1, ()), (1, 2, ()), (2, 3, ()), (3, 1, ()), (3, 4, ())],
+        );
+
+        let Err(Cycle(cycle)) = topological_sort(&g) else {
+            panic!("The graph has a cycle.");
+        };
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert!(is_cycle(&g, &cycle));
+    }
+
+    #[test]
+    fn self_loop() {
+        let g = Graph::from_directed_edges(2, vec![(0, 1, ()), (1, 1, ())]);
+
+        assert_eq!(find_cycle(&g), Some(vec![1]));
+    }
+}
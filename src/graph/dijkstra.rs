@@ -1,32 +1,141 @@
+use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
-struct Edge {
-    to: usize,
-    cost: i64,
-}
-
-type Graph = Vec<Vec<Edge>>;
-const INF: i64 = 1 << 60;
+use super::{Graph, Overflow, ShortestPathTree, Weight};
 
-fn dijkstra(g: &Graph, s: usize) -> Vec<i64> {
-    let mut dis = vec![INF; g.len()];
+/// Computes the shortest paths from `s` to every vertex.
+///
+/// All edge weights are required to be non-negative. If the length of a shortest path
+/// to some vertex cannot be represented by `W` then Result::Err is returned.
+pub fn dijkstra<W: Weight>(g: &Graph<W>, s: usize) -> Result<ShortestPathTree<W>, Overflow> {
+    let mut dis: Vec<Option<W>> = vec![None; g.node_count()];
+    let mut parents = vec![None; g.node_count()];
+    let mut overflowed = vec![false; g.node_count()];
     let mut bheap = BinaryHeap::new();
 
-    dis[s] = 0;
-    bheap.push((dis[s], s));
-    while let Some(peek) = bheap.pop() {
-        let v = peek.1; 
-        if dis[v] < peek.0 {
+    dis[s] = Some(W::zero());
+    bheap.push(Reverse((W::zero(), s)));
+    while let Some(Reverse((d, v))) = bheap.pop() {
+        if dis[v].is_some_and(|dv| dv < d) {
             continue;
         }
 
-        for e in g[v].iter() {
-            if dis[e.to] > dis[v] + e.cost {
-                dis[e.to] = dis[v] + e.cost;
-                bheap.push((dis[e.to], e.to));
+        for e in g.edges_from(v) {
+            assert!(
+                !e.weight.is_negative(),
+                "The weight of an edge is required to be non-negative."
+            );
+
+            // An overflowing candidate is longer than any representable distance.
+            let Some(nd) = d.checked_add(e.weight) else {
+                overflowed[e.to] = true;
+                continue;
+            };
+            if dis[e.to].is_none_or(|dt| dt > nd) {
+                dis[e.to] = Some(nd);
+                parents[e.to] = Some(v);
+                bheap.push(Reverse((nd, e.to)));
             }
         }
     }
 
-    dis
+    if (0..g.node_count()).any(|v| overflowed[v] && dis[v].is_none()) {
+        return Err(Overflow);
+    }
+
+    Ok(ShortestPathTree::new(s, dis, parents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::OrderedFloat;
+
+    #[test]
+    fn shortest_distances() {
+        let g = Graph::from_undirected_edges(
+            5,
+            vec![(0, 1, 7), (0, 2, 2), (2, 1, 3), (1, 3, 1), (2, 3, 8)],
+        );
+
+        let tree = dijkstra(&g, 0).unwrap();
+        assert_eq!(
+            tree.distances(),
+            &[Some(0), Some(5), Some(2), Some(6), None]
+        );
+        assert_eq!(tree.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(tree.path_to(4), None);
+
+        let tree = dijkstra(&g, 3).unwrap();
+        assert_eq!(
+            tree.distances(),
+            &[Some(6), Some(1), Some(4), Some(0), None]
+        );
+        assert_eq!(tree.parent(3), None);
+        assert_eq!(tree.parent(2), Some(1));
+    }
+
+    #[test]
+    fn float_weights() {
+        let g = Graph::from_directed_edges(
+            3,
+            vec![
+                (0, 1, OrderedFloat(0.5)),
+                (1, 2, OrderedFloat(0.25)),
+                (0, 2, OrderedFloat(1.0)),
+            ],
+        );
+
+        let tree = dijkstra(&g, 0).unwrap();
+        assert_eq!(tree.distance(2), Some(OrderedFloat(0.75)));
+        assert_eq!(tree.path_to(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn negative_zero_weight() {
+        let g = Graph::from_directed_edges(
+            3,
+            vec![(0, 1, OrderedFloat(-0.0)), (1, 2, OrderedFloat(0.5))],
+        );
+
+        let tree = dijkstra(&g, 0).unwrap();
+        assert_eq!(tree.distance(2), Some(OrderedFloat(0.5)));
+        assert_eq!(tree.path_to(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn lexicographic_weights() {
+        // (distance, hops): among the shortest routes, the one with fewest hops is chosen.
+        let g = Graph::from_directed_edges(
+            4,
+            vec![
+                (0, 1, (1u32, 1u32)),
+                (1, 2, (1, 1)),
+                (2, 3, (1, 1)),
+                (0, 3, (3, 1)),
+            ],
+        );
+
+        let tree = dijkstra(&g, 0).unwrap();
+        assert_eq!(tree.distance(3), Some((3, 1)));
+        assert_eq!(tree.path_to(3), Some(vec![0, 3]));
+    }
+
+    #[test]
+    fn overflow() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 200u8), (1, 2, 100)]);
+
+        assert_eq!(dijkstra(&g, 0), Err(Overflow));
+
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 200u8), (1, 2, 100), (0, 2, 250)]);
+        assert_eq!(dijkstra(&g, 0).unwrap().distance(2), Some(250));
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_weight() {
+        let g = Graph::from_directed_edges(2, vec![(0, 1, -1)]);
+        let _ = dijkstra(&g, 0);
+    }
 }
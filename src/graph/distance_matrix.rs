@@ -0,0 +1,59 @@
+use super::Distance;
+
+/// The result of an all-pairs shortest path search.
+///
+/// It holds the distance between every pair of vertices and the predecessors on the
+/// shortest paths, so that the paths themselves can be reconstructed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMatrix<W> {
+    distances: Vec<Vec<Distance<W>>>,
+    parents: Vec<Vec<Option<usize>>>,
+}
+
+impl<W: Copy> DistanceMatrix<W> {
+    pub(crate) fn new(distances: Vec<Vec<Distance<W>>>, parents: Vec<Vec<Option<usize>>>) -> Self {
+        debug_assert_eq!(distances.len(), parents.len());
+
+        Self { distances, parents }
+    }
+
+    /// Returns the number of vertices.
+    pub fn node_count(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Returns the distance from `u` to `v`.
+    pub fn distance(&self, u: usize, v: usize) -> Distance<W> {
+        self.distances[u][v]
+    }
+
+    /// Returns the distances from `u` to all vertices.
+    pub fn distances_from(&self, u: usize) -> &[Distance<W>] {
+        &self.distances[u]
+    }
+
+    /// Returns true if the graph has a negative cycle.
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.node_count()).any(|v| matches!(self.distances[v][v], Distance::NegativeInfinity))
+    }
+
+    /// Returns the vertices on a shortest path from `u` to `v`, both ends included.
+    ///
+    /// If `v` is unreachable from `u` or their distance is negative infinity then
+    /// Option::None is returned.
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        let Distance::Finite(_) = self.distances[u][v] else {
+            return None;
+        };
+
+        let mut path = vec![v];
+        let mut w = v;
+        while w != u {
+            w = self.parents[u][w].expect("A vertex on a finite path must have a parent.");
+            path.push(w);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
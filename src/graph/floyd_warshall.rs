@@ -0,0 +1,171 @@
+use super::{Distance, DistanceMatrix, Graph, Overflow, Weight};
+
+/// Computes the shortest paths between all pairs of vertices in O(V^3).
+///
+/// Negative edge weights are allowed. The pairs connected through a negative cycle get
+/// `Distance::NegativeInfinity`. If the length of a shortest path cannot be represented
+/// by `W` then Result::Err is returned, except that an underflowing distance is reported
+/// as negative infinity when the graph has a negative cycle.
+pub fn floyd_warshall<W: Weight>(g: &Graph<W>) -> Result<DistanceMatrix<W>, Overflow> {
+    let n = g.node_count();
+    let mut dis = vec![vec![Distance::Unreachable; n]; n];
+    let mut parents = vec![vec![None; n]; n];
+    let mut overflowed = vec![vec![false; n]; n];
+    let mut underflowed = false;
+
+    for (v, row) in dis.iter_mut().enumerate() {
+        row[v] = Distance::Finite(W::zero());
+    }
+    for e in (0..n).flat_map(|u| g.edges_from(u)) {
+        if Distance::Finite(e.weight) < dis[e.from][e.to] {
+            dis[e.from][e.to] = Distance::Finite(e.weight);
+            parents[e.from][e.to] = Some(e.from);
+        }
+    }
+
+    for k in 0..n {
+        // Keeps the distances bounded once a negative cycle through k is found.
+        if dis[k][k] < Distance::Finite(W::zero()) {
+            dis[k][k] = Distance::NegativeInfinity;
+        }
+
+        for i in 0..n {
+            let dik = dis[i][k];
+            if dik == Distance::Unreachable {
+                continue;
+            }
+
+            for j in 0..n {
+                let candidate = match (dik, dis[k][j]) {
+                    (_, Distance::Unreachable) => continue,
+                    (Distance::Finite(a), Distance::Finite(b)) => match a.checked_add(b) {
+                        Some(c) => Distance::Finite(c),
+                        // An overflowing candidate is longer than any representable distance.
+                        None if a >= W::zero() => {
+                            overflowed[i][j] = true;
+                            continue;
+                        }
+                        // An underflowing candidate is a path through a negative cycle
+                        // unless the distance itself cannot be represented.
+                        None => {
+                            underflowed = true;
+                            Distance::NegativeInfinity
+                        }
+                    },
+                    _ => Distance::NegativeInfinity,
+                };
+
+                if candidate < dis[i][j] {
+                    dis[i][j] = candidate;
+                    parents[i][j] = parents[k][j];
+                }
+            }
+        }
+    }
+
+    // Every pair connected through a vertex on a negative cycle has no shortest path.
+    let negative: Vec<usize> = (0..n)
+        .filter(|&k| dis[k][k] < Distance::Finite(W::zero()))
+        .collect();
+    if negative.is_empty() && underflowed {
+        return Err(Overflow);
+    }
+    for k in negative {
+        for i in 0..n {
+            if dis[i][k] == Distance::Unreachable {
+                continue;
+            }
+
+            for j in 0..n {
+                if dis[k][j] != Distance::Unreachable {
+                    dis[i][j] = Distance::NegativeInfinity;
+                    parents[i][j] = None;
+                }
+            }
+        }
+    }
+
+    for i in 0..n {
+        for j in 0..n {
+            if overflowed[i][j] && dis[i][j] == Distance::Unreachable {
+                return Err(Overflow);
+            }
+        }
+    }
+
+    Ok(DistanceMatrix::new(dis, parents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Distance::{Finite, NegativeInfinity, Unreachable};
+
+    #[test]
+    fn negative_edges_without_cycle() {
+        let g = Graph::from_directed_edges(
+            4,
+            vec![
+                (0, 1, 3),
+                (0, 2, 8),
+                (1, 3, 1),
+                (2, 1, 4),
+                (3, 0, 2),
+                (3, 2, -5),
+            ],
+        );
+
+        let matrix = floyd_warshall(&g).unwrap();
+        assert!(!matrix.has_negative_cycle());
+        assert_eq!(
+            matrix.distances_from(0),
+            &[Finite(0), Finite(3), Finite(-1), Finite(4)]
+        );
+        assert_eq!(
+            matrix.distances_from(2),
+            &[Finite(7), Finite(4), Finite(0), Finite(5)]
+        );
+        assert_eq!(matrix.path(0, 2), Some(vec![0, 1, 3, 2]));
+        assert_eq!(matrix.path(2, 0), Some(vec![2, 1, 3, 0]));
+        assert_eq!(matrix.path(1, 1), Some(vec![1]));
+    }
+
+    #[test]
+    fn unreachable_pairs() {
+        let g = Graph::from_undirected_edges(3, vec![(0, 1, 2u32)]);
+
+        let matrix = floyd_warshall(&g).unwrap();
+        assert_eq!(matrix.distance(1, 0), Finite(2));
+        assert_eq!(matrix.distance(0, 2), Unreachable);
+        assert_eq!(matrix.path(0, 2), None);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // 1 -> 2 -> 1 is a negative cycle.
+        let g = Graph::from_directed_edges(4, vec![(0, 1, 1), (1, 2, -3), (2, 1, 1), (2, 3, 1)]);
+
+        let matrix = floyd_warshall(&g).unwrap();
+        assert!(matrix.has_negative_cycle());
+        assert_eq!(matrix.distance(0, 0), Finite(0));
+        assert_eq!(matrix.distance(0, 3), NegativeInfinity);
+        assert_eq!(matrix.distance(3, 1), Unreachable);
+        assert_eq!(matrix.path(0, 3), None);
+    }
+
+    #[test]
+    fn long_negative_cycle_does_not_overflow() {
+        let n = 100;
+        let g = Graph::from_directed_edges(n, (0..n).map(|v| (v, (v + 1) % n, -1i8)));
+
+        let matrix = floyd_warshall(&g).unwrap();
+        assert_eq!(matrix.distance(0, n - 1), NegativeInfinity);
+    }
+
+    #[test]
+    fn overflow() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 200u8), (1, 2, 100)]);
+
+        assert_eq!(floyd_warshall(&g), Err(Overflow));
+    }
+}
@@ -0,0 +1,188 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{bellman_ford, dijkstra, Distance, DistanceMatrix, Graph, Overflow, Weight};
+
+/// The error returned by `johnson`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JohnsonError {
+    /// The graph has a negative cycle, which is given as in `BellmanFord::negative_cycle`.
+    NegativeCycle(Vec<usize>),
+    Overflow,
+}
+
+/// Computes the shortest paths between all pairs of vertices in O(VE log V).
+///
+/// Negative edge weights are allowed. The edges are reweighted to be non-negative by the
+/// potentials computed by `bellman_ford`, and then `dijkstra` is run from every vertex.
+/// If the graph has a negative cycle then Result::Err is returned.
+///
+/// The reweighted weights `w(u, v) + h(u) - h(v)` and the reweighted distances, where
+/// `h` is the distance from the virtual vertex, have to be representable by `W` as well.
+/// They can exceed every distance of the graph, in which case `JohnsonError::Overflow` is
+/// returned even if all distances are representable. For example, the edge `u -> v` of
+/// weight 100 is reweighted to 200 if `h(u) = 0` and `h(v) = -100`.
+pub fn johnson<W: Weight>(g: &Graph<W>) -> Result<DistanceMatrix<W>, JohnsonError> {
+    let n = g.node_count();
+
+    // Adds a virtual vertex connected to every vertex by an edge of weight zero.
+    let mut extended = Graph::new_directed(n + 1);
+    for e in (0..n).flat_map(|u| g.edges_from(u)) {
+        extended.add_edge(e.from, e.to, e.weight);
+    }
+    for v in 0..n {
+        extended.add_edge(n, v, W::zero());
+    }
+
+    let result = bellman_ford(&extended, n)?;
+    if let Some(cycle) = result.negative_cycle() {
+        return Err(JohnsonError::NegativeCycle(cycle.to_vec()));
+    }
+    let potentials: Vec<W> = (0..n)
+        .map(|v| match result.distance(v) {
+            Distance::Finite(h) => h,
+            _ => unreachable!("Every vertex is reachable from the virtual vertex."),
+        })
+        .collect();
+
+    let mut reweighted = Graph::new_directed(n);
+    for e in (0..n).flat_map(|u| g.edges_from(u)) {
+        // `w + h(u)` does not overflow, since it is not less than `h(v)` if `w` is negative,
+        // and the potentials are not positive. So only an unrepresentable result overflows.
+        let weight = e
+            .weight
+            .checked_add(potentials[e.from])
+            .and_then(|w| w.checked_sub(potentials[e.to]))
+            .ok_or(Overflow)?;
+        // Clamps a tiny negative weight caused by the rounding of floating point numbers.
+        reweighted.add_edge(e.from, e.to, weight.max(W::zero()));
+    }
+
+    let mut distances = Vec::with_capacity(n);
+    let mut parents = Vec::with_capacity(n);
+    for u in 0..n {
+        let tree = dijkstra(&reweighted, u)?;
+        let row = tree
+            .distances()
+            .iter()
+            .enumerate()
+            .map(|(v, d)| match d {
+                // `d + h(v)` does not overflow, since `d` is not negative and `h(v)` is not
+                // positive. So only an unrepresentable distance overflows.
+                Some(d) => d
+                    .checked_add(potentials[v])
+                    .and_then(|d| d.checked_sub(potentials[u]))
+                    .map(Distance::Finite)
+                    .ok_or(Overflow),
+                None => Ok(Distance::Unreachable),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        distances.push(row);
+        parents.push(tree.parents().to_vec());
+    }
+
+    Ok(DistanceMatrix::new(distances, parents))
+}
+
+impl From<Overflow> for JohnsonError {
+    fn from(_: Overflow) -> Self {
+        JohnsonError::Overflow
+    }
+}
+
+impl fmt::Display for JohnsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JohnsonError::NegativeCycle(cycle) => {
+                write!(f, "the graph has a negative cycle {:?}", cycle)
+            }
+            JohnsonError::Overflow => Overflow.fmt(f),
+        }
+    }
+}
+
+impl Error for JohnsonError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::floyd_warshall;
+    use crate::graph::OrderedFloat;
+
+    #[test]
+    fn same_as_floyd_warshall() {
+        let g = Graph::from_directed_edges(
+            6,
+            vec![
+                (0, 1, 3),
+                (0, 2, 8),
+                (1, 3, 1),
+                (2, 1, 4),
+                (3, 0, 2),
+                (3, 2, -5),
+                (4, 5, -2),
+                (1, 4, 7),
+            ],
+        );
+
+        let matrix = johnson(&g).unwrap();
+        assert_eq!(matrix.path(0, 2), Some(vec![0, 1, 3, 2]));
+        assert_eq!(matrix.path(5, 0), None);
+
+        let expected = floyd_warshall(&g).unwrap();
+        for u in 0..6 {
+            assert_eq!(matrix.distances_from(u), expected.distances_from(u));
+            for v in 0..6 {
+                assert_eq!(matrix.path(u, v).is_some(), expected.path(u, v).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn float_weights() {
+        let g = Graph::from_directed_edges(
+            3,
+            vec![
+                (0, 1, OrderedFloat(0.1)),
+                (1, 2, OrderedFloat(-0.3)),
+                (0, 2, OrderedFloat(0.0)),
+            ],
+        );
+
+        let matrix = johnson(&g).unwrap();
+        assert_eq!(matrix.path(0, 2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn narrow_weights() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, -100i8), (1, 2, -27)]);
+        let matrix = johnson(&g).unwrap();
+        assert_eq!(matrix.distance(0, 2), Distance::Finite(-127));
+        assert_eq!(
+            matrix.distances_from(1),
+            floyd_warshall(&g).unwrap().distances_from(1)
+        );
+
+        // The edge 0 -> 1 is reweighted to 200, although the distance is 100.
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 100i8), (2, 1, -100)]);
+        assert_eq!(
+            floyd_warshall(&g).unwrap().distance(0, 1),
+            Distance::Finite(100)
+        );
+        assert_eq!(johnson(&g), Err(JohnsonError::Overflow));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let g = Graph::from_directed_edges(3, vec![(0, 1, 1), (1, 2, -3), (2, 1, 1)]);
+
+        match johnson(&g) {
+            Err(JohnsonError::NegativeCycle(mut cycle)) => {
+                cycle.sort();
+                assert_eq!(cycle, vec![1, 2]);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::{Edge, Graph, Overflow, Weight};
+use crate::others::UnionFind;
+
+/// A minimum spanning forest, which is a minimum spanning tree if the graph is connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningForest<W> {
+    node_count: usize,
+    edges: Vec<Edge<W>>,
+    total_weight: W,
+}
+
+/// Computes a minimum spanning forest by Kruskal's algorithm in O(E log E).
+///
+/// The edges are regarded as undirected. If the total weight cannot be represented by `W`
+/// then Result::Err is returned.
+pub fn kruskal<W: Weight>(g: &Graph<W>) -> Result<SpanningForest<W>, Overflow> {
+    let mut edges: Vec<&Edge<W>> = g.edges().collect();
+    edges.sort_by_key(|e| e.weight);
+
+    let mut uf = UnionFind::new(g.node_count());
+    let mut forest = SpanningForest::new(g.node_count());
+    for e in edges {
+        if uf.unite(e.from, e.to) {
+            forest.push(*e)?;
+        }
+    }
+
+    Ok(forest)
+}
+
+/// Computes a minimum spanning forest by Prim's algorithm in O(E log V).
+///
+/// The edges are regarded as undirected. If the total weight cannot be represented by `W`
+/// then Result::Err is returned.
+pub fn prim<W: Weight>(g: &Graph<W>) -> Result<SpanningForest<W>, Overflow> {
+    let undirected;
+    let g = if g.is_directed() {
+        undirected = Graph::from_undirected_edges(
+            g.node_count(),
+            g.edges().map(|e| (e.from, e.to, e.weight)),
+        );
+        &undirected
+    } else {
+        g
+    };
+
+    let mut visited = vec![false; g.node_count()];
+    let mut forest = SpanningForest::new(g.node_count());
+    let mut bheap = BinaryHeap::new();
+    for s in 0..g.node_count() {
+        if visited[s] {
+            continue;
+        }
+
+        visited[s] = true;
+        bheap.extend(g.edges_from(s).map(|e| Reverse((e.weight, e.from, e.to))));
+        while let Some(Reverse((weight, from, to))) = bheap.pop() {
+            if visited[to] {
+                continue;
+            }
+
+            visited[to] = true;
+            forest.push(Edge::new(from, to, weight))?;
+            bheap.extend(
+                g.edges_from(to)
+                    .filter(|e| !visited[e.to])
+                    .map(|e| Reverse((e.weight, e.from, e.to))),
+            );
+        }
+    }
+
+    Ok(forest)
+}
+
+impl<W: Weight> SpanningForest<W> {
+    fn new(node_count: usize) -> Self {
+        Self {
+            node_count,
+            edges: Vec::new(),
+            total_weight: W::zero(),
+        }
+    }
+
+    fn push(&mut self, edge: Edge<W>) -> Result<(), Overflow> {
+        self.total_weight = self.total_weight.checked_add(edge.weight).ok_or(Overflow)?;
+        self.edges.push(edge);
+
+        Ok(())
+    }
+
+    /// Returns the edges of the forest in the order they were chosen.
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+
+    /// Returns the sum of the weights of the edges.
+    pub fn total_weight(&self) -> W {
+        self.total_weight
+    }
+
+    /// Returns the number of trees, which equals the number of connected components.
+    pub fn tree_count(&self) -> usize {
+        self.node_count - self.edges.len()
+    }
+
+    /// Returns true if the forest is a single spanning tree.
+    pub fn is_spanning_tree(&self) -> bool {
+        self.tree_count() <= 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> Graph<i32> {
+        Graph::from_undirected_edges(
+            6,
+            vec![
+                (0, 1, 4),
+                (0, 2, 3),
+                (1, 2, 1),
+                (1, 3, 2),
+                (2, 3, 4),
+                (3, 4, 2),
+                (4, 5, 6),
+                (3, 5, 7),
+            ],
+        )
+    }
+
+    fn spans(forest: &SpanningForest<i32>, node_count: usize) -> bool {
+        let mut uf = UnionFind::new(node_count);
+        forest.edges().iter().for_each(|e| {
+            uf.unite(e.from, e.to);
+        });
+        uf.count_components() == 1
+    }
+
+    #[test]
+    fn kruskal_tree() {
+        let forest = kruskal(&sample_graph()).unwrap();
+
+        assert_eq!(forest.total_weight(), 14);
+        assert_eq!(forest.edges().len(), 5);
+        assert!(forest.is_spanning_tree());
+        assert!(spans(&forest, 6));
+    }
+
+    #[test]
+    fn prim_tree() {
+        let forest = prim(&sample_graph()).unwrap();
+
+        assert_eq!(forest.total_weight(), 14);
+        assert!(forest.is_spanning_tree());
+        assert!(spans(&forest, 6));
+    }
+
+    #[test]
+    fn spanning_forest() {
+        let g = Graph::from_directed_edges(
+            5,
+            vec![(0, 1, 5u32), (1, 0, 1), (2, 3, 2), (3, 4, 3), (2, 4, 1)],
+        );
+
+        for forest in [kruskal(&g).unwrap(), prim(&g).unwrap()] {
+            assert_eq!(forest.total_weight(), 4);
+            assert_eq!(forest.tree_count(), 2);
+            assert!(!forest.is_spanning_tree());
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let g = Graph::from_undirected_edges(3, vec![(0, 1, 200u8), (1, 2, 100)]);
+
+        assert_eq!(kruskal(&g), Err(Overflow));
+        assert_eq!(prim(&g), Err(Overflow));
+    }
+}
@@ -0,0 +1,165 @@
+use super::{dfs_forest, dfs_forest_from, Graph};
+
+/// The strongly connected components of a graph.
+///
+/// The components are numbered in a topological order of the condensation, so that every
+/// edge between different components goes from a smaller id to a larger one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StronglyConnectedComponents {
+    component_ids: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+/// Decomposes the graph into strongly connected components by Kosaraju's algorithm.
+///
+/// The components of an undirected graph are its connected components.
+pub fn strongly_connected_components<W: Clone>(g: &Graph<W>) -> StronglyConnectedComponents {
+    let n = g.node_count();
+    let order = dfs_forest(g).post_order().to_vec();
+
+    // Each tree of the search on the reversed graph, started in the decreasing order of
+    // finish time, is a component.
+    let search = dfs_forest_from(&g.reversed(), order.into_iter().rev());
+    let mut component_ids = vec![0; n];
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    for &v in search.pre_order() {
+        let id = match search.parent(v) {
+            Some(p) => component_ids[p],
+            None => {
+                components.push(Vec::new());
+                components.len() - 1
+            }
+        };
+
+        component_ids[v] = id;
+        components[id].push(v);
+    }
+
+    StronglyConnectedComponents {
+        component_ids,
+        components,
+    }
+}
+
+impl StronglyConnectedComponents {
+    /// Returns the number of components.
+    pub fn count(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns the id of the component containing `v`.
+    pub fn component_id(&self, v: usize) -> usize {
+        self.component_ids[v]
+    }
+
+    /// Returns the vertices of every component, indexed by id.
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+
+    /// Returns true if `u` and `v` are in the same component.
+    pub fn same(&self, u: usize, v: usize) -> bool {
+        self.component_ids[u] == self.component_ids[v]
+    }
+
+    /// Builds the condensation of `g`, the DAG whose vertices are the components.
+    ///
+    /// There is an edge between two components if some edge of `g` connects them. The
+    /// parallel edges are merged and the weights are dropped.
+    pub fn condensation<W>(&self, g: &Graph<W>) -> Graph<()> {
+        let mut edges: Vec<(usize, usize, ())> = g
+            .edges()
+            .map(|e| (self.component_ids[e.from], self.component_ids[e.to], ()))
+            .filter(|&(u, v, _)| u != v)
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        Graph::from_directed_edges(self.count(), edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::topological_sort;
+
+    fn sorted(components: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        components
+            .iter()
+            .map(|c| {
+                let mut c = c.clone();
+                c.sort();
+                c
+            })
+            .collect()
+    }
+
+    #[test]
+    fn components_in_topological_order() {
+        // {0, 1, 2} -> {3, 4} -> {5}, {0, 1, 2} -> {6}
+        let g = Graph::from_directed_edges(
+            7,
+            vec![
+                (0, 1, ()),
+                (1, 2, ()),
+                (2, 0, ()),
+                (2, 3, ()),
+                (3, 4, ()),
+                (4, 3, ()),
+                (4, 5, ()),
+                (1, 6, ()),
+            ],
+        );
+
+        let scc = strongly_connected_components(&g);
+        assert_eq!(scc.count(), 4);
+        assert!(scc.same(0, 2));
+        assert!(!scc.same(2, 3));
+        assert!(g
+            .edges()
+            .all(|e| scc.component_id(e.from) <= scc.component_id(e.to)));
+
+        let mut components = sorted(scc.components());
+        components.sort();
+        assert_eq!(
+            components,
+            vec![vec![0, 1, 2], vec![3, 4], vec![5], vec![6]]
+        );
+    }
+
+    #[test]
+    fn condensation_is_dag() {
+        let g = Graph::from_directed_edges(
+            5,
+            vec![
+                (0, 1, 1),
+                (1, 0, 2),
+                (1, 2, 3),
+                (0, 2, 4),
+                (2, 3, 5),
+                (3, 2, 6),
+                (4, 4, 7),
+            ],
+        );
+
+        let scc = strongly_connected_components(&g);
+        let dag = scc.condensation(&g);
+        assert_eq!(dag.node_count(), 3);
+        assert_eq!(dag.edge_count(), 1);
+        assert_eq!(
+            dag.neighbors(scc.component_id(0)).collect::<Vec<_>>(),
+            vec![scc.component_id(2)]
+        );
+        assert!(topological_sort(&dag).is_ok());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        let g = Graph::from_undirected_edges(4, vec![(0, 1, ()), (2, 3, ())]);
+
+        let scc = strongly_connected_components(&g);
+        assert_eq!(scc.count(), 2);
+        assert!(scc.same(2, 3));
+    }
+}
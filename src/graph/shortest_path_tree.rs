@@ -0,0 +1,83 @@
+/// The result of a single-source shortest path search.
+///
+/// It holds the distance from the source to every vertex and the predecessor of every
+/// vertex on a shortest path, so that the paths themselves can be reconstructed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPathTree<W> {
+    source: usize,
+    distances: Vec<Option<W>>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<W> ShortestPathTree<W> {
+    pub(crate) fn new(
+        source: usize,
+        distances: Vec<Option<W>>,
+        parents: Vec<Option<usize>>,
+    ) -> Self {
+        debug_assert_eq!(distances.len(), parents.len());
+
+        Self {
+            source,
+            distances,
+            parents,
+        }
+    }
+
+    /// Returns the source vertex of the search.
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Returns the distance from the source to `v`.
+    ///
+    /// If `v` is unreachable from the source then Option::None is returned.
+    pub fn distance(&self, v: usize) -> Option<W>
+    where
+        W: Copy,
+    {
+        self.distances[v]
+    }
+
+    /// Returns the distances from the source to all vertices.
+    pub fn distances(&self) -> &[Option<W>] {
+        &self.distances
+    }
+
+    /// Returns true if `v` is reachable from the source.
+    pub fn is_reachable(&self, v: usize) -> bool {
+        self.distances[v].is_some()
+    }
+
+    /// Returns the vertex preceding `v` on a shortest path from the source.
+    ///
+    /// Option::None is returned for the source itself and for unreachable vertices.
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    /// Returns the predecessors of all vertices.
+    pub fn parents(&self) -> &[Option<usize>] {
+        &self.parents
+    }
+
+    /// Returns the vertices on a shortest path from the source to `target`, both ends included.
+    ///
+    /// If `target` is unreachable from the source then Option::None is returned.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        if !self.is_reachable(target) {
+            return None;
+        }
+
+        let mut path = vec![target];
+        let mut v = target;
+        while let Some(p) = self.parents[v] {
+            path.push(p);
+            v = p;
+        }
+        path.reverse();
+
+        debug_assert_eq!(path.first(), Some(&self.source));
+        Some(path)
+    }
+}
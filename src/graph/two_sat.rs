@@ -0,0 +1,131 @@
+use super::{strongly_connected_components, Graph};
+
+/// A 2-satisfiability problem over boolean variables `0..n`.
+///
+/// Each clause `(x == a) || (y == b)` is encoded as the implications `x != a -> y == b`
+/// and `y != b -> x == a`, and the problem is solved through the strongly connected
+/// components of the implication graph.
+#[derive(Debug, Clone)]
+pub struct TwoSat {
+    variable_count: usize,
+    implications: Graph<()>,
+}
+
+impl TwoSat {
+    /// Constructs a new problem with `variable_count` variables and no clauses.
+    pub fn new(variable_count: usize) -> Self {
+        Self {
+            variable_count,
+            implications: Graph::new_directed(2 * variable_count),
+        }
+    }
+
+    /// Returns the number of variables.
+    pub fn variable_count(&self) -> usize {
+        self.variable_count
+    }
+
+    /// Adds the clause `(x == a) || (y == b)`.
+    pub fn add_clause(&mut self, x: usize, a: bool, y: usize, b: bool) {
+        let (x_true, x_false) = (self.literal(x, a), self.literal(x, !a));
+        let (y_true, y_false) = (self.literal(y, b), self.literal(y, !b));
+
+        self.implications.add_edge(x_false, y_true, ());
+        self.implications.add_edge(y_false, x_true, ());
+    }
+
+    /// Returns an assignment satisfying every clause, or Option::None if there is none.
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let scc = strongly_connected_components(&self.implications);
+
+        // A literal implied by its negation must be true, which holds if its component
+        // comes later in the topological order.
+        (0..self.variable_count)
+            .map(|x| {
+                let t = scc.component_id(self.literal(x, true));
+                let f = scc.component_id(self.literal(x, false));
+                (t != f).then_some(t > f)
+            })
+            .collect()
+    }
+
+    // Returns the vertex of the implication graph standing for `x == value`.
+    fn literal(&self, x: usize, value: bool) -> usize {
+        assert!(
+            x < self.variable_count,
+            "The variable {} is out of range.",
+            x
+        );
+        2 * x + usize::from(!value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(clauses: &[(usize, bool, usize, bool)], assignment: &[bool]) -> bool {
+        clauses
+            .iter()
+            .all(|&(x, a, y, b)| assignment[x] == a || assignment[y] == b)
+    }
+
+    #[test]
+    fn satisfiable() {
+        let clauses = [
+            (0, true, 1, false),
+            (1, true, 2, true),
+            (0, false, 2, false),
+            (2, true, 2, true),
+        ];
+        let mut problem = TwoSat::new(3);
+        for &(x, a, y, b) in clauses.iter() {
+            problem.add_clause(x, a, y, b);
+        }
+
+        let assignment = problem.solve().unwrap();
+        assert!(satisfies(&clauses, &assignment));
+        assert!(assignment[2]);
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let mut problem = TwoSat::new(2);
+        problem.add_clause(0, true, 1, true);
+        problem.add_clause(0, false, 1, true);
+        problem.add_clause(0, true, 1, false);
+        problem.add_clause(0, false, 1, false);
+
+        assert_eq!(problem.solve(), None);
+    }
+
+    #[test]
+    fn exhaustive() {
+        // Checks every set of clauses over two variables.
+        let literals: Vec<(usize, bool)> = vec![(0, true), (0, false), (1, true), (1, false)];
+        let all_clauses: Vec<(usize, bool, usize, bool)> = (0..literals.len())
+            .flat_map(|i| (i..literals.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (literals[i].0, literals[i].1, literals[j].0, literals[j].1))
+            .collect();
+
+        for mask in 0..(1u32 << all_clauses.len()) {
+            let clauses: Vec<_> = (0..all_clauses.len())
+                .filter(|i| mask >> i & 1 == 1)
+                .map(|i| all_clauses[i])
+                .collect();
+
+            let mut problem = TwoSat::new(2);
+            for &(x, a, y, b) in clauses.iter() {
+                problem.add_clause(x, a, y, b);
+            }
+
+            let expected = [[false, false], [false, true], [true, false], [true, true]]
+                .iter()
+                .any(|assignment| satisfies(&clauses, assignment));
+            match problem.solve() {
+                Some(assignment) => assert!(satisfies(&clauses, &assignment)),
+                None => assert!(!expected),
+            }
+        }
+    }
+}
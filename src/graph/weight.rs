@@ -0,0 +1,240 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+/// A type usable as an edge weight of shortest path algorithms.
+///
+/// Weights are totally ordered, have an additive identity, and report overflow on
+/// addition and subtraction instead of wrapping.
+pub trait Weight: Copy + Ord {
+    /// Returns the additive identity.
+    fn zero() -> Self;
+
+    /// Adds two weights.
+    ///
+    /// If the sum cannot be represented then Option::None is returned.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Subtracts `rhs` from `self`.
+    ///
+    /// If the difference cannot be represented then Option::None is returned.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Returns true if the weight is less than zero.
+    ///
+    /// A weight which is equal in value to zero, such as the negative zero of floating
+    /// point numbers, is not negative even if it is ordered below `zero()`.
+    fn is_negative(self) -> bool {
+        self < Self::zero()
+    }
+
+    /// Returns true if the weight is equal in value to zero.
+    ///
+    /// The negative zero of floating point numbers is zero, even if it is ordered below
+    /// `zero()`.
+    fn is_zero(self) -> bool {
+        self == Self::zero()
+    }
+}
+
+/// The error returned when the length of a path cannot be represented by its weight type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+/// A floating point number ordered by `total_cmp`, so that it can be used as a `Weight`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderedFloat<F>(pub F);
+
+macro_rules! impl_weight_for_integer {
+    ( $($t : ty),* ) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_weight_for_float {
+    ( $($t : ty),* ) => {
+        $(
+            impl PartialEq for OrderedFloat<$t> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for OrderedFloat<$t> {}
+
+            impl PartialOrd for OrderedFloat<$t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for OrderedFloat<$t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+
+            impl Weight for OrderedFloat<$t> {
+                fn zero() -> Self {
+                    OrderedFloat(0.0)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    Self::checked(self, rhs, self.0 + rhs.0)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    Self::checked(self, rhs, self.0 - rhs.0)
+                }
+
+                fn is_negative(self) -> bool {
+                    self.0 < 0.0
+                }
+
+                fn is_zero(self) -> bool {
+                    self.0 == 0.0
+                }
+            }
+
+            impl OrderedFloat<$t> {
+                // The result of an operation on finite numbers overflows to an infinity.
+                fn checked(lhs: Self, rhs: Self, result: $t) -> Option<Self> {
+                    if result.is_finite() || !(lhs.0.is_finite() && rhs.0.is_finite()) {
+                        Some(OrderedFloat(result))
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_float!(f32, f64);
+
+// Tuples are compared lexicographically and added element-wise.
+impl<A: Weight, B: Weight> Weight for (A, B) {
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some((self.0.checked_add(rhs.0)?, self.1.checked_add(rhs.1)?))
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some((self.0.checked_sub(rhs.0)?, self.1.checked_sub(rhs.1)?))
+    }
+
+    fn is_negative(self) -> bool {
+        self.0.is_negative() || (self.0 <= A::zero() && self.1.is_negative())
+    }
+
+    fn is_zero(self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<A: Weight, B: Weight, C: Weight> Weight for (A, B, C) {
+    fn zero() -> Self {
+        (A::zero(), B::zero(), C::zero())
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some((
+            self.0.checked_add(rhs.0)?,
+            self.1.checked_add(rhs.1)?,
+            self.2.checked_add(rhs.2)?,
+        ))
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some((
+            self.0.checked_sub(rhs.0)?,
+            self.1.checked_sub(rhs.1)?,
+            self.2.checked_sub(rhs.2)?,
+        ))
+    }
+
+    fn is_negative(self) -> bool {
+        self.0.is_negative()
+            || (self.0 <= A::zero()
+                && (self.1.is_negative() || (self.1 <= B::zero() && self.2.is_negative())))
+    }
+
+    fn is_zero(self) -> bool {
+        self.0.is_zero() && self.1.is_zero() && self.2.is_zero()
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the length of a path overflowed its weight type")
+    }
+}
+
+impl Error for Overflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_weight() {
+        assert_eq!(<u32 as Weight>::zero(), 0);
+        assert_eq!(Weight::checked_add(3u8, 4), Some(7));
+        assert_eq!(Weight::checked_add(200u8, 56), None);
+        assert_eq!(Weight::checked_add(i64::MIN, -1), None);
+        assert_eq!(Weight::checked_sub(3u8, 4), None);
+        assert_eq!(Weight::checked_sub(3i8, 4), Some(-1));
+    }
+
+    #[test]
+    fn float_weight() {
+        let x = OrderedFloat(1.5f64);
+
+        assert_eq!(x.checked_add(OrderedFloat(2.0)), Some(OrderedFloat(3.5)));
+        assert_eq!(
+            OrderedFloat(f64::MAX).checked_add(OrderedFloat(f64::MAX)),
+            None
+        );
+        assert_eq!(
+            OrderedFloat(-f64::MAX).checked_sub(OrderedFloat(f64::MAX)),
+            None
+        );
+        assert!(OrderedFloat(-1.0f32) < OrderedFloat::zero());
+        assert!(OrderedFloat(-1.0f32).is_negative());
+        assert!(!OrderedFloat(-0.0f64).is_negative());
+        assert!(OrderedFloat(-0.0f64).is_zero());
+        assert!(!OrderedFloat(f64::MIN_POSITIVE).is_zero());
+        assert!(!OrderedFloat(f64::INFINITY).is_negative());
+    }
+
+    #[test]
+    fn tuple_weight() {
+        assert!((1u32, 9u32) < (2, 0));
+        assert_eq!((1u32, 2u32).checked_add((3, 4)), Some((4, 6)));
+        assert_eq!((1u8, 255u8).checked_add((0, 1)), None);
+        assert!((0i32, -1i32).is_negative());
+        assert!(!(1i32, -1i32).is_negative());
+        assert!(!(OrderedFloat(-0.0f64), 1u32).is_negative());
+        assert!((OrderedFloat(-0.0f64), 0u32).is_zero());
+        assert!((OrderedFloat(-0.0f64), 0u32, -1i8).is_negative());
+    }
+}
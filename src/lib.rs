@@ -1,5 +1,6 @@
-mod basic;
-mod tree;
-mod sort;
-mod others;
-mod graph;
+pub mod basic;
+pub mod tree;
+pub mod sort;
+pub mod others;
+pub mod graph;
+pub mod prelude;
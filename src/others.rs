@@ -1,2 +1,9 @@
+pub mod union_find;
+pub mod weighted_union_find;
+pub mod rollback_union_find;
+pub mod dynamic_connectivity;
 
-mod union_find;
+pub use union_find::UnionFind;
+pub use weighted_union_find::{AbelianGroup, Contradiction, WeightedUnionFind};
+pub use rollback_union_find::{RollbackUnionFind, Snapshot};
+pub use dynamic_connectivity::OfflineDynamicConnectivity;
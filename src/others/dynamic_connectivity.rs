@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use super::RollbackUnionFind;
+
+/// Answers connectivity queries on a graph whose edges are added and removed, offline.
+///
+/// The lifetime of every edge is an interval of the operations, which is stored in a
+/// segment tree over time. The tree is traversed with a `RollbackUnionFind`, so that all
+/// queries are answered in O(Q log Q log N).
+pub struct OfflineDynamicConnectivity {
+    node_count: usize,
+    operations: Vec<Operation>,
+}
+
+enum Operation {
+    Add(usize, usize),
+    Remove(usize, usize),
+    Connected(usize, usize),
+}
+
+impl OfflineDynamicConnectivity {
+    /// Constructs a new solver for a graph with `node_count` vertices and no edges.
+    pub fn new(node_count: usize) -> Self {
+        Self {
+            node_count,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Adds an undirected edge between `u` and `v`.
+    ///
+    /// Parallel edges are allowed and each of them must be removed separately.
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        self.check_vertex(u);
+        self.check_vertex(v);
+        self.operations.push(Operation::Add(u.min(v), u.max(v)));
+    }
+
+    /// Removes an undirected edge between `u` and `v`, which is required to exist.
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        self.check_vertex(u);
+        self.check_vertex(v);
+        self.operations.push(Operation::Remove(u.min(v), u.max(v)));
+    }
+
+    /// Asks whether `u` and `v` are connected at this point.
+    pub fn connected(&mut self, u: usize, v: usize) {
+        self.check_vertex(u);
+        self.check_vertex(v);
+        self.operations.push(Operation::Connected(u, v));
+    }
+
+    /// Returns the answers to the `connected` queries in the order they were asked.
+    pub fn solve(&self) -> Vec<bool> {
+        let time = self.operations.len();
+        if time == 0 {
+            return Vec::new();
+        }
+
+        let mut segments = vec![Vec::new(); 2 * time.next_power_of_two()];
+        let mut open_edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (t, operation) in self.operations.iter().enumerate() {
+            match *operation {
+                Operation::Add(u, v) => open_edges.entry((u, v)).or_default().push(t),
+                Operation::Remove(u, v) => {
+                    let added = open_edges
+                        .get_mut(&(u, v))
+                        .and_then(|times| times.pop())
+                        .unwrap_or_else(|| panic!("The edge ({}, {}) does not exist.", u, v));
+                    Self::insert(&mut segments, 1, 0, time, added, t, (u, v));
+                }
+                Operation::Connected(..) => (),
+            }
+        }
+        for (&edge, times) in open_edges.iter() {
+            for &added in times {
+                Self::insert(&mut segments, 1, 0, time, added, time, edge);
+            }
+        }
+
+        let mut uf = RollbackUnionFind::new(self.node_count);
+        let mut answers = Vec::new();
+        self.traverse(&segments, 1, 0, time, &mut uf, &mut answers);
+
+        answers
+    }
+
+    // Adds `edge` to the nodes of the segment tree covering [l, r).
+    fn insert(
+        segments: &mut [Vec<(usize, usize)>],
+        node: usize,
+        node_l: usize,
+        node_r: usize,
+        l: usize,
+        r: usize,
+        edge: (usize, usize),
+    ) {
+        if r <= node_l || node_r <= l {
+            return;
+        }
+        if l <= node_l && node_r <= r {
+            segments[node].push(edge);
+            return;
+        }
+
+        let mid = (node_l + node_r) / 2;
+        Self::insert(segments, 2 * node, node_l, mid, l, r, edge);
+        Self::insert(segments, 2 * node + 1, mid, node_r, l, r, edge);
+    }
+
+    // Visits the leaves in increasing order of time, holding the edges alive at each leaf.
+    fn traverse(
+        &self,
+        segments: &[Vec<(usize, usize)>],
+        node: usize,
+        node_l: usize,
+        node_r: usize,
+        uf: &mut RollbackUnionFind,
+        answers: &mut Vec<bool>,
+    ) {
+        let snapshot = uf.snapshot();
+        for &(u, v) in segments[node].iter() {
+            uf.unite(u, v);
+        }
+
+        if node_r - node_l == 1 {
+            if let Operation::Connected(u, v) = self.operations[node_l] {
+                answers.push(uf.same(u, v));
+            }
+        } else {
+            let mid = (node_l + node_r) / 2;
+            self.traverse(segments, 2 * node, node_l, mid, uf, answers);
+            self.traverse(segments, 2 * node + 1, mid, node_r, uf, answers);
+        }
+
+        uf.rollback(snapshot);
+    }
+
+    fn check_vertex(&self, v: usize) {
+        assert!(v < self.node_count, "The vertex {} is out of range.", v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::others::UnionFind;
+
+    #[test]
+    fn add_and_remove_edges() {
+        let mut solver = OfflineDynamicConnectivity::new(4);
+
+        solver.connected(0, 1);
+        solver.add_edge(0, 1);
+        solver.add_edge(1, 2);
+        solver.connected(0, 2);
+        solver.remove_edge(1, 0);
+        solver.connected(0, 2);
+        solver.connected(1, 2);
+        solver.add_edge(2, 3);
+        solver.add_edge(3, 0);
+        solver.connected(0, 1);
+        solver.connected(3, 3);
+
+        assert_eq!(solver.solve(), vec![false, true, false, true, true, true]);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let mut solver = OfflineDynamicConnectivity::new(2);
+
+        solver.add_edge(0, 1);
+        solver.add_edge(1, 0);
+        solver.remove_edge(0, 1);
+        solver.connected(0, 1);
+        solver.remove_edge(0, 1);
+        solver.connected(0, 1);
+
+        assert_eq!(solver.solve(), vec![true, false]);
+    }
+
+    #[test]
+    fn same_as_naive() {
+        let n = 8;
+        let mut solver = OfflineDynamicConnectivity::new(n);
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut expected = Vec::new();
+
+        // A deterministic pseudo-random sequence of operations.
+        let mut seed: u64 = 12345;
+        let mut next = |m: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % m
+        };
+        for _ in 0..500 {
+            match next(3) {
+                0 => {
+                    let (u, v) = (next(n), next(n));
+                    solver.add_edge(u, v);
+                    edges.push((u, v));
+                }
+                1 if !edges.is_empty() => {
+                    let (u, v) = edges.swap_remove(next(edges.len()));
+                    solver.remove_edge(u, v);
+                }
+                _ => {
+                    let (u, v) = (next(n), next(n));
+                    solver.connected(u, v);
+
+                    let mut uf = UnionFind::new(n);
+                    for &(a, b) in edges.iter() {
+                        uf.unite(a, b);
+                    }
+                    expected.push(uf.same(u, v));
+                }
+            }
+        }
+
+        assert_eq!(solver.solve(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "The vertex 2 is out of range.")]
+    fn remove_edge_out_of_range() {
+        let mut solver = OfflineDynamicConnectivity::new(2);
+
+        solver.remove_edge(0, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_missing_edge() {
+        let mut solver = OfflineDynamicConnectivity::new(2);
+
+        solver.remove_edge(0, 1);
+        solver.solve();
+    }
+}
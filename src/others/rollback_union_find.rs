@@ -0,0 +1,161 @@
+use std::mem;
+
+/// A union-find whose unions can be undone.
+///
+/// It uses union by size without path compression, so that every `unite` changes O(1)
+/// entries and `root` takes O(log n).
+pub struct RollbackUnionFind {
+    parent_indexs: Vec<usize>,
+    sizes: Vec<usize>,
+    component_count: usize,
+    // The roots attached under another root, in the order of the unions.
+    history: Vec<usize>,
+}
+
+/// A state of `RollbackUnionFind` to which it can be rolled back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot(usize);
+
+impl RollbackUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent_indexs: (0..size).collect(),
+            sizes: vec![1; size],
+            component_count: size,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn root(&self, x: usize) -> usize {
+        let mut x = x;
+        while self.parent_indexs[x] != x {
+            x = self.parent_indexs[x];
+        }
+
+        x
+    }
+
+    /// Merges the sets containing `x` and `y`.
+    ///
+    /// Returns true if the sets were different and have been merged.
+    pub fn unite(&mut self, x: usize, y: usize) -> bool {
+        let mut rx = self.root(x);
+        let mut ry = self.root(y);
+
+        if rx == ry {
+            return false;
+        }
+
+        if self.sizes[rx] > self.sizes[ry] {
+            mem::swap(&mut rx, &mut ry);
+        }
+        self.parent_indexs[rx] = ry;
+        self.sizes[ry] += self.sizes[rx];
+        self.component_count -= 1;
+        self.history.push(rx);
+
+        true
+    }
+
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// Returns the number of elements in the set containing `x`.
+    pub fn size(&self, x: usize) -> usize {
+        self.sizes[self.root(x)]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count_components(&self) -> usize {
+        self.component_count
+    }
+
+    /// Returns the current state.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history.len())
+    }
+
+    /// Undoes the last union which merged two sets.
+    ///
+    /// Returns false if there is no union to be undone.
+    pub fn undo(&mut self) -> bool {
+        let Some(rx) = self.history.pop() else {
+            return false;
+        };
+
+        let ry = self.parent_indexs[rx];
+        self.parent_indexs[rx] = rx;
+        self.sizes[ry] -= self.sizes[rx];
+        self.component_count += 1;
+
+        true
+    }
+
+    /// Undoes every union made after `snapshot` was taken.
+    ///
+    /// The snapshot is required not to be taken after a state which has been rolled back.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        assert!(
+            snapshot.0 <= self.history.len(),
+            "The snapshot is newer than the current state."
+        );
+
+        while self.history.len() > snapshot.0 {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unite_and_rollback() {
+        let mut uf = RollbackUnionFind::new(5);
+
+        uf.unite(0, 1);
+        let snapshot = uf.snapshot();
+
+        assert!(uf.unite(2, 3));
+        assert!(uf.unite(1, 3));
+        assert!(!uf.unite(0, 2));
+        assert!(uf.same(0, 2));
+        assert_eq!(uf.size(3), 4);
+        assert_eq!(uf.count_components(), 2);
+
+        uf.rollback(snapshot);
+        assert!(uf.same(0, 1));
+        assert!(!uf.same(0, 2));
+        assert!(!uf.same(2, 3));
+        assert_eq!(uf.size(0), 2);
+        assert_eq!(uf.count_components(), 4);
+    }
+
+    #[test]
+    fn undo() {
+        let mut uf = RollbackUnionFind::new(3);
+
+        uf.unite(0, 1);
+        uf.unite(1, 2);
+        assert!(uf.undo());
+        assert!(uf.same(0, 1));
+        assert!(!uf.same(1, 2));
+        assert!(uf.undo());
+        assert!(!uf.undo());
+        assert_eq!(uf.count_components(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rollback_to_discarded_snapshot() {
+        let mut uf = RollbackUnionFind::new(3);
+
+        let before = uf.snapshot();
+        uf.unite(0, 1);
+        let after = uf.snapshot();
+        uf.rollback(before);
+        uf.rollback(after);
+    }
+}
@@ -1,41 +1,105 @@
-struct UnionFind {
+use std::mem;
+
+pub struct UnionFind {
     parent_indexs: Vec<usize>,
+    sizes: Vec<usize>,
+    component_count: usize,
 }
 
 impl UnionFind {
-    fn new(size: usize) -> Self {
+    pub fn new(size: usize) -> Self {
         let mut parent_indexs = vec![0; size];
         for (i, idx) in parent_indexs.iter_mut().enumerate().take(size) {
             *idx = i;
         }
 
-        Self { parent_indexs }
+        Self {
+            parent_indexs,
+            sizes: vec![1; size],
+            component_count: size,
+        }
     }
 
-    fn root(&mut self, x: usize) -> usize {
-        if self.parent_indexs[x] == x {
-            x
-        } else {
-            self.parent_indexs[x] = self.root(self.parent_indexs[x]);
-            self.parent_indexs[x]
+    /// Returns the representative of the set containing `x`.
+    ///
+    /// Every node on the path from `x` is attached directly to the root. The path is
+    /// followed iteratively, so that a long path does not overflow the stack.
+    pub fn root(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent_indexs[root] != root {
+            root = self.parent_indexs[root];
+        }
+
+        let mut x = x;
+        while self.parent_indexs[x] != root {
+            let parent = self.parent_indexs[x];
+            self.parent_indexs[x] = root;
+            x = parent;
         }
+
+        root
     }
 
-    fn unite(&mut self, x: usize, y: usize) {
-        let rx = self.root(x);
-        let ry = self.root(y);
+    /// Merges the sets containing `x` and `y`.
+    ///
+    /// The smaller set is attached under the root of the larger one. Returns true if the
+    /// sets were different and have been merged.
+    pub fn unite(&mut self, x: usize, y: usize) -> bool {
+        let mut rx = self.root(x);
+        let mut ry = self.root(y);
+
+        if rx == ry {
+            return false;
+        }
 
-        if rx != ry {
-            self.parent_indexs[rx] = ry;
+        if self.sizes[rx] > self.sizes[ry] {
+            mem::swap(&mut rx, &mut ry);
         }
+        self.parent_indexs[rx] = ry;
+        self.sizes[ry] += self.sizes[rx];
+        self.component_count -= 1;
+
+        true
     }
 
-    fn same(&mut self, x: usize, y: usize) -> bool {
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
         let rx = self.root(x);
         let ry = self.root(y);
 
         rx == ry
     }
+
+    /// Returns the number of elements in the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let rx = self.root(x);
+        self.sizes[rx]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count_components(&self) -> usize {
+        self.component_count
+    }
+
+    /// Returns the members of every set.
+    ///
+    /// The members of each set are in increasing order, and the sets are ordered by their
+    /// smallest members.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.parent_indexs.len();
+        let mut group_indexs = vec![None; n];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.component_count);
+
+        for x in 0..n {
+            let rx = self.root(x);
+            let idx = *group_indexs[rx].get_or_insert_with(|| {
+                groups.push(Vec::with_capacity(self.sizes[rx]));
+                groups.len() - 1
+            });
+            groups[idx].push(x);
+        }
+
+        groups
+    }
 }
 
 #[cfg(test)]
@@ -58,4 +122,72 @@ mod tests {
         uf.unite(1, 2);
         assert!(uf.same(0, 3));
     }
+
+    #[test]
+    fn size_and_count_components() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.count_components(), 6);
+
+        assert!(uf.unite(0, 1));
+        assert!(uf.unite(2, 3));
+        assert!(uf.unite(3, 4));
+        assert!(!uf.unite(2, 4));
+
+        assert_eq!(uf.size(0), 2);
+        assert_eq!(uf.size(4), 3);
+        assert_eq!(uf.size(5), 1);
+        assert_eq!(uf.count_components(), 3);
+    }
+
+    #[test]
+    fn union_by_size() {
+        let mut uf = UnionFind::new(4);
+
+        uf.unite(0, 1);
+        uf.unite(0, 2);
+        let root = uf.root(0);
+
+        // The single element is attached under the larger set regardless of the order.
+        uf.unite(3, 0);
+        assert_eq!(uf.root(3), root);
+    }
+
+    #[test]
+    fn root_of_long_chain() {
+        let n = 3_000_000;
+        let mut uf = UnionFind::new(n);
+
+        // Builds the degenerate chain n - 1 -> n - 2 -> ... -> 0 directly.
+        for i in 1..n {
+            uf.parent_indexs[i] = i - 1;
+        }
+
+        assert_eq!(uf.root(n - 1), 0);
+        assert!((0..n).all(|i| uf.parent_indexs[i] == 0));
+    }
+
+    #[test]
+    fn unite_many_elements() {
+        let n = 3_000_000;
+        let mut uf = UnionFind::new(n);
+
+        for i in 1..n {
+            assert!(uf.unite(i - 1, i));
+        }
+
+        assert_eq!(uf.count_components(), 1);
+        assert_eq!(uf.size(n / 2), n);
+        assert!(uf.same(0, n - 1));
+    }
+
+    #[test]
+    fn groups() {
+        let mut uf = UnionFind::new(6);
+
+        uf.unite(4, 1);
+        uf.unite(5, 0);
+        uf.unite(1, 3);
+
+        assert_eq!(uf.groups(), vec![vec![0, 5], vec![1, 3, 4], vec![2]]);
+    }
 }
@@ -0,0 +1,272 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Debug;
+use std::mem;
+
+/// An abelian group, whose elements can be used as potentials of `WeightedUnionFind`.
+pub trait AbelianGroup: Copy + Eq {
+    /// Returns the identity element.
+    fn identity() -> Self;
+
+    /// Combines two elements. The operation is required to be associative and commutative.
+    fn op(self, rhs: Self) -> Self;
+
+    /// Returns the inverse element.
+    fn inverse(self) -> Self;
+}
+
+/// A union-find which maintains the differences of potentials between elements.
+///
+/// `unite(x, y, d)` records that the potential of `y` is that of `x` plus `d`.
+pub struct WeightedUnionFind<G: AbelianGroup> {
+    parent_indexs: Vec<usize>,
+    sizes: Vec<usize>,
+    // The potential of each element relative to its parent.
+    potentials: Vec<G>,
+    component_count: usize,
+}
+
+/// The error returned when a constraint contradicts the recorded ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction<G> {
+    /// The difference derived from the recorded constraints.
+    pub existing: G,
+    /// The difference given to `unite`.
+    pub requested: G,
+}
+
+// Integers form groups under wrapping addition.
+macro_rules! impl_abelian_group_for_integer {
+    ( $($t : ty),* ) => {
+        $(
+            impl AbelianGroup for $t {
+                fn identity() -> Self {
+                    0
+                }
+
+                fn op(self, rhs: Self) -> Self {
+                    self.wrapping_add(rhs)
+                }
+
+                fn inverse(self) -> Self {
+                    self.wrapping_neg()
+                }
+            }
+        )*
+    };
+}
+
+impl_abelian_group_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<A: AbelianGroup, B: AbelianGroup> AbelianGroup for (A, B) {
+    fn identity() -> Self {
+        (A::identity(), B::identity())
+    }
+
+    fn op(self, rhs: Self) -> Self {
+        (self.0.op(rhs.0), self.1.op(rhs.1))
+    }
+
+    fn inverse(self) -> Self {
+        (self.0.inverse(), self.1.inverse())
+    }
+}
+
+impl<G: AbelianGroup> WeightedUnionFind<G> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent_indexs: (0..size).collect(),
+            sizes: vec![1; size],
+            potentials: vec![G::identity(); size],
+            component_count: size,
+        }
+    }
+
+    /// Returns the representative of the set containing `x`.
+    ///
+    /// Every node on the path from `x` is attached directly to the root. The path is
+    /// followed iteratively, so that a long path does not overflow the stack.
+    pub fn root(&mut self, x: usize) -> usize {
+        // The first pass finds the root and the potential of `x` relative to it.
+        let mut root = x;
+        let mut potential = G::identity();
+        while self.parent_indexs[root] != root {
+            potential = potential.op(self.potentials[root]);
+            root = self.parent_indexs[root];
+        }
+
+        // The second pass attaches the nodes to the root. The potential of the parent of a
+        // node relative to the root is that of the node minus the potential to the parent.
+        let mut x = x;
+        while self.parent_indexs[x] != root {
+            let parent = self.parent_indexs[x];
+            let to_parent = self.potentials[x];
+            self.parent_indexs[x] = root;
+            self.potentials[x] = potential;
+            potential = potential.op(to_parent.inverse());
+            x = parent;
+        }
+
+        root
+    }
+
+    /// Returns the potential of `x` relative to the root of its set.
+    pub fn potential(&mut self, x: usize) -> G {
+        self.root(x);
+        self.potentials[x]
+    }
+
+    /// Records that the potential of `y` is that of `x` plus `diff`, merging their sets.
+    ///
+    /// Returns Result::Ok(true) if the sets have been merged and Result::Ok(false) if the
+    /// constraint was already implied. If the constraint contradicts the recorded ones then
+    /// nothing is changed and Result::Err is returned.
+    pub fn unite(&mut self, x: usize, y: usize, diff: G) -> Result<bool, Contradiction<G>> {
+        let (mut rx, mut ry) = (self.root(x), self.root(y));
+        let (px, py) = (self.potentials[x], self.potentials[y]);
+
+        if rx == ry {
+            let existing = py.op(px.inverse());
+            return if existing == diff {
+                Ok(false)
+            } else {
+                Err(Contradiction {
+                    existing,
+                    requested: diff,
+                })
+            };
+        }
+
+        // The potential of ry relative to rx.
+        let mut d = diff.op(px).op(py.inverse());
+        if self.sizes[rx] < self.sizes[ry] {
+            mem::swap(&mut rx, &mut ry);
+            d = d.inverse();
+        }
+        self.parent_indexs[ry] = rx;
+        self.potentials[ry] = d;
+        self.sizes[rx] += self.sizes[ry];
+        self.component_count -= 1;
+
+        Ok(true)
+    }
+
+    /// Returns the potential of `y` minus that of `x`.
+    ///
+    /// If `x` and `y` are in different sets then Option::None is returned.
+    pub fn diff(&mut self, x: usize, y: usize) -> Option<G> {
+        if !self.same(x, y) {
+            return None;
+        }
+
+        Some(self.potentials[y].op(self.potentials[x].inverse()))
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// Returns the number of elements in the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let rx = self.root(x);
+        self.sizes[rx]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count_components(&self) -> usize {
+        self.component_count
+    }
+}
+
+impl<G: Debug> fmt::Display for Contradiction<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the difference {:?} contradicts the recorded difference {:?}",
+            self.requested, self.existing
+        )
+    }
+}
+
+impl<G: Debug> Error for Contradiction<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unite_and_diff() {
+        let mut uf = WeightedUnionFind::new(5);
+
+        assert_eq!(uf.unite(0, 1, 5), Ok(true));
+        assert_eq!(uf.unite(2, 1, 3), Ok(true));
+        assert_eq!(uf.unite(3, 4, -2), Ok(true));
+
+        assert_eq!(uf.diff(0, 1), Some(5));
+        assert_eq!(uf.diff(1, 0), Some(-5));
+        assert_eq!(uf.diff(0, 2), Some(2));
+        assert_eq!(uf.diff(0, 3), None);
+
+        assert_eq!(uf.unite(2, 3, 10), Ok(true));
+        assert_eq!(uf.diff(0, 4), Some(10));
+        assert_eq!(uf.count_components(), 1);
+        assert_eq!(uf.size(4), 5);
+    }
+
+    #[test]
+    fn contradiction() {
+        let mut uf = WeightedUnionFind::new(3);
+
+        uf.unite(0, 1, 1).unwrap();
+        uf.unite(1, 2, 1).unwrap();
+
+        assert_eq!(uf.unite(0, 2, 2), Ok(false));
+        assert_eq!(
+            uf.unite(2, 0, 1),
+            Err(Contradiction {
+                existing: -2,
+                requested: 1,
+            })
+        );
+        assert_eq!(uf.diff(0, 2), Some(2));
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 1_000_000;
+        let mut uf = WeightedUnionFind::new(n);
+
+        for i in 1..n {
+            uf.unite(i, i - 1, 1i64).unwrap();
+        }
+
+        assert_eq!(uf.diff(n - 1, 0), Some(n as i64 - 1));
+    }
+
+    #[test]
+    fn path_compression() {
+        // A path 4 -> 3 -> 2 -> 1 -> 0, where each node is 1 above its parent.
+        let mut uf = WeightedUnionFind::new(5);
+        for v in 1..5 {
+            uf.parent_indexs[v] = v - 1;
+            uf.potentials[v] = 1i32;
+        }
+
+        assert_eq!(uf.root(3), 0);
+        assert_eq!(uf.parent_indexs, vec![0, 0, 0, 0, 3]);
+        assert_eq!(uf.potentials, vec![0, 1, 2, 3, 1]);
+        assert_eq!(uf.potential(4), 4);
+        assert_eq!(uf.diff(1, 4), Some(3));
+    }
+
+    #[test]
+    fn product_group() {
+        let mut uf = WeightedUnionFind::new(3);
+
+        uf.unite(0, 1, (1u8, 255u8)).unwrap();
+        uf.unite(1, 2, (2, 2)).unwrap();
+
+        assert_eq!(uf.diff(0, 2), Some((3, 1)));
+        assert_eq!(uf.diff(2, 0), Some((253, 255)));
+    }
+}
@@ -0,0 +1,15 @@
+//! Re-exports the commonly used items of this crate.
+//!
+//! ```
+//! use algorithms_by_rust::prelude::*;
+//!
+//! let mut stack = Stack::new();
+//! stack.push(1);
+//! assert_eq!(stack.pop(), Some(1));
+//! ```
+
+pub use crate::basic::{binary_search, Queue, Stack};
+pub use crate::graph::{dijkstra, Edge, Graph, OrderedFloat, ShortestPathTree, Weight};
+pub use crate::others::UnionFind;
+pub use crate::sort::{heap_sort, merge_sort, quick_sort};
+pub use crate::tree::{AvlMap, BinaryTree, OrderedSet, RedBlackTree, AVL};
@@ -1,3 +1,7 @@
-mod heap;
-mod quick;
-mod merge;
+pub mod heap;
+pub mod quick;
+pub mod merge;
+
+pub use heap::sort as heap_sort;
+pub use quick::sort as quick_sort;
+pub use merge::sort as merge_sort;
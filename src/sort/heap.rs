@@ -101,6 +101,11 @@ impl<'a, T: Ord> Node<'a, T> {
     }
 }
 
+/// Sorts a vector in ascending order by heap sort.
+pub fn sort<T: Ord>(array: Vec<T>) -> Vec<T> {
+    Heap::new(array).sort()
+}
+
 struct Heap<T: Ord> {
     data: Vec<T>,
     tree_size: usize,
@@ -116,7 +121,7 @@ impl<T: Ord> Heap<T> {
     }
 
     pub fn sort(mut self) -> Vec<T> {
-        for i in (1..self.tree_size).rev() {
+        for _ in (1..self.tree_size).rev() {
             self.swap_root_and_tail();
             self.get_node(0).fix_root();
         }
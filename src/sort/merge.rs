@@ -61,7 +61,8 @@ fn merge<T: Ord + Debug>(mut array_x: Vec<T>, mut array_y: Vec<T>) -> Vec<T> {
     output
 }
 
-fn sort<T: Ord + Debug>(array: Vec<T>) -> Vec<T> {
+/// Sorts a vector in ascending order by merge sort.
+pub fn sort<T: Ord + Debug>(array: Vec<T>) -> Vec<T> {
     let length = array.len();
     if length < 2 {
         return array;
@@ -61,7 +61,8 @@ fn partition<T: Ord + Debug> (mut array: Vec<T>, mut pivot_idx: usize) -> (Vec<T
     }
 }
 
-fn sort<T: Ord + Debug>(array: Vec<T>) -> Vec<T> {
+/// Sorts a vector in ascending order by quick sort.
+pub fn sort<T: Ord + Debug>(array: Vec<T>) -> Vec<T> {
     match select_pivot(&array) {
         None => array,
         Some(pivot) => {
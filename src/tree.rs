@@ -1,4 +1,11 @@
-mod binary_tree;
-mod avl;
+pub mod binary_tree;
+pub mod avl;
+pub mod red_black_tree;
+pub mod ordered_set;
+mod cursor;
+mod iter;
 
 pub use binary_tree::BinaryTree;
+pub use avl::{AvlMap, AVL};
+pub use red_black_tree::RedBlackTree;
+pub use ordered_set::OrderedSet;
@@ -1,17 +1,43 @@
+use std::borrow::Borrow;
 use std::cmp;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::mem;
+use std::ops::RangeBounds;
 
+use super::iter::sorted_dedup;
+use super::OrderedSet;
+
+mod cursor;
+mod iter;
+pub mod map;
+
+pub use cursor::{Cursor, CursorMut};
+pub use iter::{IntoIter, Iter, Range};
+pub use map::AvlMap;
+
+#[allow(clippy::upper_case_acronyms)]
 pub struct AVL<T: Ord> {
     root: NodeOption<T>,
+    // If true then equal values are kept in the same node instead of being dropped.
+    multiset: bool,
+    // The number of rotations performed on the tree.
+    rotations: usize,
 }
 
 struct NodeOption<T: Ord> {
     node_opt: Option<Node<T>>,
 }
 
-struct Node<T: Ord> {
+pub(crate) struct Node<T: Ord> {
     value: T,
+    // The values equal to `value` which were added after it, in order of addition.
+    // This is always empty unless the tree is a multiset.
+    duplicates: VecDeque<T>,
+    // The height and the number of values of the subtree rooted at the node, which are
+    // kept up to date by `update` whenever the children change.
+    height: i32,
+    size: usize,
     lhs: Box<NodeOption<T>>,
     rhs: Box<NodeOption<T>>,
 }
@@ -28,18 +54,60 @@ impl<T: Ord> AVL<T> {
     pub fn new() -> Self {
         Self {
             root: NodeOption::new(),
+            multiset: false,
+            rotations: 0,
         }
     }
 
-    /// Creates a AVL<T> from  slice
+    /// Constructs a new, empty AVL<T> which keeps every copy of equal values.
+    ///
+    /// The copies of a value share one node, so the tree is balanced by the number of
+    /// distinct values. They are iterated in order of addition.
+    pub fn new_multiset() -> Self {
+        Self {
+            root: NodeOption::new(),
+            multiset: true,
+            rotations: 0,
+        }
+    }
+
+    /// Returns true if the tree keeps every copy of equal values.
+    pub fn is_multiset(&self) -> bool {
+        self.multiset
+    }
+
+    /// Returns the number of rotations performed to keep the tree balanced.
+    ///
+    /// A double rotation is counted as two. The rotations performed by `split`, `join`
+    /// and the set operations are counted in the returned tree, or in the left one for
+    /// `split`, together with those of the given trees.
+    pub fn rotation_count(&self) -> usize {
+        self.rotations
+    }
+
+    /// Constructs an AVL<T> from a slice of values in any order.
+    ///
+    /// To construct a tree from any iterator of values, use `collect` or `extend`.
     pub fn from_slice(array: &[T]) -> Self
     where
-        T: Copy,
+        T: Clone,
     {
-        array.iter().fold(Self::new(), |mut avl, v| {
-            avl.add(*v);
-            avl
-        })
+        array.iter().cloned().collect()
+    }
+
+    /// Constructs a perfectly balanced AVL<T> from values in ascending order in O(n).
+    ///
+    /// Equal values are dropped except the first one, as by `add`. The values are
+    /// required to be in ascending order.
+    pub fn from_sorted_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values = sorted_dedup(iter);
+
+        let len = values.len();
+        Self {
+            root: NodeOption::from_sorted(&mut values.into_iter(), len),
+            multiset: false,
+            rotations: 0,
+        }
     }
 
     /// Finds a node for a given value.
@@ -47,21 +115,397 @@ impl<T: Ord> AVL<T> {
     /// If the value is found then Option::Some is returned, containing the matching
     /// value. If the value is not found then Option::None is returned.
     pub fn find(&self, value: &T) -> Option<&T> {
-        self.root.find(value)
+        self.root.find(value, |value| value).map(|node| &node.value)
+    }
+
+    /// Returns the number of copies of a given value in the tree.
+    ///
+    /// Unless the tree is a multiset, the return value is either zero or one.
+    pub fn count(&self, value: &T) -> usize {
+        self.root
+            .find(value, |value| value)
+            .map_or(0, |node| node.count())
     }
 
     /// Adds a node containing a given value.
+    ///
+    /// If the tree is a multiset and an equal value is already contained, then the value
+    /// is kept as another copy of it. Otherwise the value is dropped.
     pub fn add(&mut self, value: T) {
-        self.root.add(value);
+        self.root.add(value, self.multiset, &mut self.rotations);
     }
 
     /// Removes a node containing a given value.
     ///
     /// If the value is found then the node containing it is removed and Option::Some
     /// is returned, containing the matching value. If the value is not found then
-    /// Option::None is returned.
+    /// Option::None is returned. All copies of the value are removed.
     pub fn remove(&mut self, value: &T) -> Option<T> {
-        self.root.remove(value)
+        self.root
+            .remove(value, |value| value, &mut self.rotations)
+            .map(|node| node.value)
+    }
+
+    /// Removes one copy of a given value, which was added first of the copies.
+    ///
+    /// If the value is found then Option::Some is returned, containing the removed
+    /// copy. If the value is not found then Option::None is returned.
+    pub fn remove_one(&mut self, value: &T) -> Option<T> {
+        self.root
+            .remove_one(value, |value| value, &mut self.rotations)
+    }
+
+    /// Removes all copies of a given value, and returns them in order of addition.
+    pub fn remove_all(&mut self, value: &T) -> Vec<T> {
+        self.root
+            .remove(value, |value| value, &mut self.rotations)
+            .map_or_else(Vec::new, |mut node| {
+                node.duplicates.push_front(node.value);
+                node.duplicates.into()
+            })
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.root.get_size()
+    }
+
+    /// Returns true if the tree contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.root.as_ref().is_none()
+    }
+
+    /// Returns the `k`-th smallest value in the tree, counting from zero.
+    ///
+    /// If `k` is not less than the number of values then Option::None is returned.
+    pub fn nth(&self, k: usize) -> Option<&T> {
+        let mut k = k;
+        let mut node = self.root.as_ref()?;
+
+        loop {
+            let lhs_size = node.lhs.get_size();
+            if k < lhs_size {
+                node = (*node.lhs).as_ref()?;
+            } else if k < lhs_size + node.count() {
+                return Some(node.get(k - lhs_size));
+            } else {
+                k -= lhs_size + node.count();
+                node = (*node.rhs).as_ref()?;
+            }
+        }
+    }
+
+    /// Returns the number of values in the tree less than a given value.
+    pub fn rank<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.rank(value, <T as Borrow<Q>>::borrow)
+    }
+
+    /// Returns the smallest value in the tree.
+    pub fn first(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(lhs) = (*node.lhs).as_ref() {
+            node = lhs;
+        }
+
+        Some(&node.value)
+    }
+
+    /// Returns the greatest value in the tree.
+    pub fn last(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(rhs) = (*node.rhs).as_ref() {
+            node = rhs;
+        }
+
+        Some(node.get(node.count() - 1))
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator over the values within a given range, in ascending order.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range::new(&self.root, range, <T as Borrow<Q>>::borrow)
+    }
+
+    /// Returns a cursor pointing at the smallest value not less than a given value.
+    ///
+    /// If there is no such value, then the cursor points at the ghost position.
+    pub fn lower_bound(&self, value: &T) -> Cursor<'_, T> {
+        Cursor::lower_bound(self, value)
+    }
+
+    /// Returns a cursor pointing at the smallest value greater than a given value.
+    ///
+    /// If there is no such value, then the cursor points at the ghost position.
+    pub fn upper_bound(&self, value: &T) -> Cursor<'_, T> {
+        Cursor::upper_bound(self, value)
+    }
+
+    /// Returns a mutable cursor pointing at the smallest value not less than a given value.
+    pub fn lower_bound_mut(&mut self, value: &T) -> CursorMut<'_, T> {
+        CursorMut::lower_bound(self, value)
+    }
+
+    /// Returns a mutable cursor pointing at the smallest value greater than a given value.
+    pub fn upper_bound_mut(&mut self, value: &T) -> CursorMut<'_, T> {
+        CursorMut::upper_bound(self, value)
+    }
+
+    /// Splits the tree into the values less than a given value and the rest in O(log n).
+    ///
+    /// Both of the returned trees are multisets if the tree is.
+    pub fn split<Q>(self, value: &Q) -> (Self, Self)
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut rotations = self.rotations;
+        let (lhs, mid, rhs) = self
+            .root
+            .split(value, <T as Borrow<Q>>::borrow, &mut rotations);
+        let rhs = match mid {
+            Some(node) => NodeOption::join(NodeOption::new(), node, rhs, &mut rotations),
+            None => rhs,
+        };
+
+        (
+            Self {
+                root: lhs,
+                multiset: self.multiset,
+                rotations,
+            },
+            Self {
+                root: rhs,
+                multiset: self.multiset,
+                rotations: 0,
+            },
+        )
+    }
+
+    /// Joins two trees into one in O(log n).
+    ///
+    /// The values of `left` are required to be less than those of `right`, and the trees
+    /// are required to be both sets or both multisets.
+    pub fn join(left: Self, right: Self) -> Self {
+        left.assert_same_kind(&right);
+        if let (Some(last), Some(first)) = (left.last(), right.first()) {
+            assert!(
+                last < first,
+                "The values of the left tree are required to be less than those of the right tree."
+            );
+        }
+
+        let mut rotations = left.rotations + right.rotations;
+        Self {
+            root: NodeOption::concat(left.root, right.root, &mut rotations),
+            multiset: left.multiset,
+            rotations,
+        }
+    }
+
+    /// Moves all values of `other` into the tree, leaving `other` empty.
+    ///
+    /// If all values of one tree are less than those of the other, then the trees are
+    /// joined in O(log n). Otherwise they are merged as `union` does. The trees are
+    /// required to be both sets or both multisets.
+    pub fn append(&mut self, other: &mut Self) {
+        self.assert_same_kind(other);
+        let is_before = match (self.last(), other.first()) {
+            (Some(last), Some(first)) => last < first,
+            _ => true,
+        };
+        let is_after = match (other.last(), self.first()) {
+            (Some(last), Some(first)) => last < first,
+            _ => true,
+        };
+
+        let lhs = mem::replace(&mut self.root, NodeOption::new());
+        let rhs = mem::replace(&mut other.root, NodeOption::new());
+        let rotations = &mut self.rotations;
+        *rotations += mem::take(&mut other.rotations);
+        self.root = if is_before {
+            NodeOption::concat(lhs, rhs, rotations)
+        } else if is_after {
+            NodeOption::concat(rhs, lhs, rotations)
+        } else {
+            lhs.union(rhs, self.multiset, rotations)
+        };
+    }
+
+    /// Returns the tree of the values contained in either of the trees.
+    ///
+    /// If the tree is a multiset then the copies of both trees are kept, those of `self`
+    /// first. Otherwise the values of `self` are kept. The trees are required to be both
+    /// sets or both multisets.
+    pub fn union(self, other: Self) -> Self {
+        self.assert_same_kind(&other);
+        let mut rotations = self.rotations + other.rotations;
+        Self {
+            root: self.root.union(other.root, self.multiset, &mut rotations),
+            multiset: self.multiset,
+            rotations,
+        }
+    }
+
+    /// Returns the tree of the values of `self` which are also contained in `other`.
+    ///
+    /// If the trees are multisets then all copies of `self` are kept for each value in
+    /// `other`, so that this is not the multiset intersection, which keeps the smaller
+    /// count. The trees are required to be both sets or both multisets.
+    pub fn intersection(self, other: Self) -> Self {
+        self.assert_same_kind(&other);
+        let mut rotations = self.rotations + other.rotations;
+        Self {
+            root: self.root.intersection(other.root, &mut rotations),
+            multiset: self.multiset,
+            rotations,
+        }
+    }
+
+    /// Returns the tree of the values of `self` which are not contained in `other`.
+    ///
+    /// If the trees are multisets then all copies of a value in `other` are removed, so
+    /// that this is not the multiset difference, which subtracts the counts. The trees are
+    /// required to be both sets or both multisets.
+    pub fn difference(self, other: Self) -> Self {
+        self.assert_same_kind(&other);
+        let mut rotations = self.rotations + other.rotations;
+        Self {
+            root: self.root.difference(other.root, &mut rotations),
+            multiset: self.multiset,
+            rotations,
+        }
+    }
+
+    // A set must not receive the copies of a multiset, which would break `add` and `remove`.
+    fn assert_same_kind(&self, other: &Self) {
+        assert_eq!(
+            self.multiset, other.multiset,
+            "The trees are required to be both sets or both multisets."
+        );
+    }
+}
+
+impl<T: Ord> Default for AVL<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> super::cursor::Tree for AVL<T> {
+    type Node = Node<T>;
+
+    fn root(&self) -> Option<&Node<T>> {
+        self.root.as_ref()
+    }
+
+    fn len(&self) -> usize {
+        AVL::len(self)
+    }
+
+    fn nth(&self, k: usize) -> Option<&T> {
+        AVL::nth(self, k)
+    }
+
+    fn rank(&self, value: &T) -> usize {
+        AVL::rank(self, value)
+    }
+
+    fn count(&self, value: &T) -> usize {
+        AVL::count(self, value)
+    }
+
+    fn remove_nth(&mut self, k: usize) -> Option<T> {
+        self.root.remove_nth(k, &mut self.rotations)
+    }
+}
+
+impl<T: Ord> super::iter::TreeNode for Node<T> {
+    type Value = T;
+
+    fn lhs(&self) -> Option<&Self> {
+        (*self.lhs).as_ref()
+    }
+
+    fn rhs(&self) -> Option<&Self> {
+        (*self.rhs).as_ref()
+    }
+
+    fn get(&self, i: usize) -> &T {
+        Node::get(self, i)
+    }
+
+    fn count(&self) -> usize {
+        Node::count(self)
+    }
+}
+
+impl<T: Ord> super::cursor::IndexedNode for Node<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<T: Ord> FromIterator<T> for AVL<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut avl = Self::new();
+        avl.extend(iter);
+        avl
+    }
+}
+
+impl<T: Ord> Extend<T> for AVL<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<T: Ord> OrderedSet<T> for AVL<T> {
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        self.root.add(value, self.multiset, &mut self.rotations)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.find(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        AVL::remove(self, value)
+    }
+
+    fn len(&self) -> usize {
+        AVL::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        AVL::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.first()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.last()
     }
 }
 
@@ -76,49 +520,166 @@ impl<T: Ord> NodeOption<T> {
         }
     }
 
-    fn find(&self, value: &T) -> Option<&T> {
+    // Builds a tree of the next `len` values of `values`, which are in ascending order.
+    // The sizes of the subtrees of every node differ by at most one, so do the heights.
+    fn from_sorted<I: Iterator<Item = T>>(values: &mut I, len: usize) -> Self {
+        if len == 0 {
+            return Self::new();
+        }
+
+        let lhs = Self::from_sorted(values, len / 2);
+        let value = values
+            .next()
+            .expect("The iterator must have `len` values left.");
+        let mut node = Node::new(value);
+        let rhs = Self::from_sorted(values, len - len / 2 - 1);
+
+        node.lhs = Box::new(lhs);
+        node.rhs = Box::new(rhs);
+        node.update();
+        Self::from_node(node)
+    }
+
+    // The values are compared with the given value through `key`, which is required to
+    // preserve the order of the values.
+    fn find<Q, F>(&self, value: &Q, key: F) -> Option<&Node<T>>
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
         let node = self.as_ref()?;
 
-        match value.cmp(&node.value) {
-            Ordering::Equal => Some(&node.value),
-            Ordering::Less => node.lhs.find(value),
-            Ordering::Greater => node.rhs.find(value),
+        match value.cmp(key(&node.value)) {
+            Ordering::Equal => Some(node),
+            Ordering::Less => node.lhs.find(value, key),
+            Ordering::Greater => node.rhs.find(value, key),
+        }
+    }
+
+    // The value must not be changed in a way which changes its order.
+    fn find_mut<Q, F>(&mut self, value: &Q, key: F) -> Option<&mut T>
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
+        let node = self.as_mut()?;
+
+        match value.cmp(key(&node.value)) {
+            Ordering::Equal => Some(&mut node.value),
+            Ordering::Less => node.lhs.find_mut(value, key),
+            Ordering::Greater => node.rhs.find_mut(value, key),
         }
     }
 
-    fn add(&mut self, value: T) {
+    fn rank<Q, F>(&self, value: &Q, key: F) -> usize
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
+        let mut rank = 0;
+        let mut node_opt = self;
+
+        while let Some(node) = node_opt.as_ref() {
+            if value <= key(&node.value) {
+                node_opt = &node.lhs;
+            } else {
+                rank += node.lhs.get_size() + node.count();
+                node_opt = &node.rhs;
+            }
+        }
+
+        rank
+    }
+
+    // Returns the rank of the value if it is in the tree, and otherwise the rank at which
+    // it would be added, in the same way as `slice::binary_search`.
+    fn search<Q, F>(&self, value: &Q, key: F) -> Result<usize, usize>
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
+        let mut rank = 0;
+        let mut node_opt = self;
+
+        while let Some(node) = node_opt.as_ref() {
+            match value.cmp(key(&node.value)) {
+                Ordering::Less => node_opt = &node.lhs,
+                Ordering::Equal => return Ok(rank + node.lhs.get_size()),
+                Ordering::Greater => {
+                    rank += node.lhs.get_size() + node.count();
+                    node_opt = &node.rhs;
+                }
+            }
+        }
+
+        Err(rank)
+    }
+
+    // The value must not be changed in a way which changes its order.
+    fn nth_mut(&mut self, k: usize) -> Option<&mut T> {
+        let node = self.as_mut()?;
+        let lhs_size = node.lhs.get_size();
+
+        if k < lhs_size {
+            node.lhs.nth_mut(k)
+        } else if k < lhs_size + node.count() {
+            Some(node.get_mut(k - lhs_size))
+        } else {
+            let count = node.count();
+            node.rhs.nth_mut(k - lhs_size - count)
+        }
+    }
+
+    // Returns true if the value has been added.
+    // An equal value is kept as a duplicate if `multiset` is true, and dropped otherwise.
+    fn add(&mut self, value: T, multiset: bool, rotations: &mut usize) -> bool {
         match self.as_mut() {
             Some(node) => match value.cmp(&node.value) {
                 Ordering::Less => {
-                    node.lhs.add(value);
-                    self.rebalance();
+                    let added = node.lhs.add(value, multiset, rotations);
+                    self.rebalance(rotations);
+                    added
                 }
                 Ordering::Greater => {
-                    node.rhs.add(value);
-                    self.rebalance();
+                    let added = node.rhs.add(value, multiset, rotations);
+                    self.rebalance(rotations);
+                    added
+                }
+                Ordering::Equal if multiset => {
+                    node.duplicates.push_back(value);
+                    node.update();
+                    true
                 }
-                Ordering::Equal => (),
+                Ordering::Equal => false,
             },
-            None => self.set(Node::new(value)),
+            None => {
+                self.set(Node::new(value));
+                true
+            }
         }
     }
 
-    fn remove(&mut self, value: &T) -> Option<T> {
+    // Returns the removed node, whose children are Option::None.
+    fn remove<Q, F>(&mut self, value: &Q, key: F, rotations: &mut usize) -> Option<Node<T>>
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
         if let Some(ref mut node) = self.as_mut() {
-            let result = match value.cmp(&node.value) {
+            let result = match value.cmp(key(&node.value)) {
                 Ordering::Less => {
-                    let result = Self::remove(&mut node.lhs, value);
-                    self.rebalance();
+                    let result = Self::remove(&mut node.lhs, value, key, rotations);
+                    self.rebalance(rotations);
 
                     result
                 }
                 Ordering::Greater => {
-                    let result = Self::remove(&mut node.rhs, value);
-                    self.rebalance();
+                    let result = Self::remove(&mut node.rhs, value, key, rotations);
+                    self.rebalance(rotations);
 
                     result
                 }
-                Ordering::Equal => self.remove_self(value),
+                Ordering::Equal => self.remove_self(rotations),
             };
 
             return result;
@@ -127,8 +688,68 @@ impl<T: Ord> NodeOption<T> {
         None
     }
 
-    fn rebalance(&mut self) {
+    // Removes the first copy of the value, and the node if it has no other copies.
+    fn remove_one<Q, F>(&mut self, value: &Q, key: F, rotations: &mut usize) -> Option<T>
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
+        let node = self.as_mut()?;
+
+        match value.cmp(key(&node.value)) {
+            Ordering::Less => {
+                let result = node.lhs.remove_one(value, key, rotations);
+                self.rebalance(rotations);
+                result
+            }
+            Ordering::Greater => {
+                let result = node.rhs.remove_one(value, key, rotations);
+                self.rebalance(rotations);
+                result
+            }
+            Ordering::Equal => match node.duplicates.pop_front() {
+                Some(next) => {
+                    node.update();
+                    Some(mem::replace(&mut node.value, next))
+                }
+                None => self.remove_self(rotations).map(|node| node.value),
+            },
+        }
+    }
+
+    // Removes the `k`-th copy of the values in the tree.
+    fn remove_nth(&mut self, k: usize, rotations: &mut usize) -> Option<T> {
+        let node = self.as_mut()?;
+        let lhs_size = node.lhs.get_size();
+        let count = node.count();
+
+        if k < lhs_size {
+            let result = node.lhs.remove_nth(k, rotations);
+            self.rebalance(rotations);
+            result
+        } else if k < lhs_size + count {
+            let result = match k - lhs_size {
+                _ if count == 1 => return self.remove_self(rotations).map(|node| node.value),
+                0 => {
+                    let next = node.duplicates.pop_front()?;
+                    Some(mem::replace(&mut node.value, next))
+                }
+                i => node.duplicates.remove(i - 1),
+            };
+            node.update();
+            result
+        } else {
+            let result = node.rhs.remove_nth(k - lhs_size - count, rotations);
+            self.rebalance(rotations);
+            result
+        }
+    }
+
+    // Updates the height and the size of the node, and rotates it if the children's heights
+    // differ by two.
+    fn rebalance(&mut self, rotations: &mut usize) {
         if let Some(node) = self.as_mut() {
+            node.update();
             let balance = node.get_balance();
 
             match balance {
@@ -136,24 +757,27 @@ impl<T: Ord> NodeOption<T> {
                     let rhs = (*node.rhs).as_ref().expect("The rhs of the node must be Option::Some because the balance of the node is positive.");
                     if rhs.get_balance() == Balance::BiasedLeft(1) {
                         node.rhs.rotate_right();
+                        *rotations += 1;
                     }
                     self.rotate_left();
+                    *rotations += 1;
                 }
                 Balance::BiasedLeft(2) => {
                     let lhs = (*node.lhs).as_ref().expect("The lhs of the node must be Option::Some because the balance of the node is negative.");
-                    if lhs.get_balance() == Balance::BiasedRight(2) {
+                    if lhs.get_balance() == Balance::BiasedRight(1) {
                         node.lhs.rotate_left();
+                        *rotations += 1;
                     }
                     self.rotate_right();
+                    *rotations += 1;
                 }
                 _ => (),
             }
         }
     }
 
-    // Removes the root of tree (self).
-    // The value of the node and the given value are required to be same.
-    fn remove_self(&mut self, value: &T) -> Option<T> {
+    // Removes the root of tree (self), and returns it with its children Option::None.
+    fn remove_self(&mut self, rotations: &mut usize) -> Option<Node<T>> {
         let node = self
             .as_mut()
             .expect("The node is required not to be Option::None.");
@@ -163,7 +787,7 @@ impl<T: Ord> NodeOption<T> {
                 let node = self
                     .take()
                     .expect("The node is required not to be Option::None.");
-                Some(node.value)
+                Some(node)
             }
             (Some(_), None) => {
                 let mut node = self
@@ -173,7 +797,7 @@ impl<T: Ord> NodeOption<T> {
                     self.set(lhs);
                 }
 
-                Some(node.value)
+                Some(node)
             }
             (None, Some(_)) => {
                 let mut node = self
@@ -183,15 +807,19 @@ impl<T: Ord> NodeOption<T> {
                     self.set(rhs);
                 }
 
-                Some(node.value)
+                Some(node)
             }
-            (Some(lhs), Some(_)) => {
-                let lhs_max_node = lhs.max_mut();
+            (Some(_), Some(_)) => {
+                // The node takes over the values of the greatest node of the lhs.
+                let mut lhs_max_node = node
+                    .lhs
+                    .remove_last(rotations)
+                    .expect("The lhs of the node is required not to be Option::None.");
                 mem::swap(&mut node.value, &mut lhs_max_node.value);
+                mem::swap(&mut node.duplicates, &mut lhs_max_node.duplicates);
+                self.rebalance(rotations);
 
-                let result = Self::remove(&mut node.lhs, value);
-                self.rebalance();
-                result
+                Some(lhs_max_node)
             }
         }
     }
@@ -210,7 +838,9 @@ impl<T: Ord> NodeOption<T> {
         if let Some(n) = rhs.lhs.take() {
             node.rhs.set(n);
         }
+        node.update();
         rhs.lhs.set(node);
+        rhs.update();
 
         *self = NodeOption::from_node(rhs);
     }
@@ -229,17 +859,166 @@ impl<T: Ord> NodeOption<T> {
         if let Some(d) = lhs.rhs.take() {
             node.lhs.set(d);
         }
+        node.update();
         lhs.rhs.set(node);
+        lhs.update();
 
         *self = NodeOption::from_node(lhs);
     }
 
+    // Joins two trees and a node between them, whose heights are not required to be close.
+    // The values of `lhs` are required to be less than the value of `mid`, and those of
+    // `rhs` greater. Runs in O(difference of the heights).
+    fn join(lhs: Self, mut mid: Node<T>, rhs: Self, rotations: &mut usize) -> Self {
+        let lhs_height = lhs.get_height();
+        let rhs_height = rhs.get_height();
+
+        if lhs_height > rhs_height + 1 {
+            let mut lhs = lhs;
+            let node = lhs
+                .as_mut()
+                .expect("The lhs is required not to be Option::None because it is taller.");
+            let inner = mem::replace(&mut *node.rhs, NodeOption::new());
+            *node.rhs = Self::join(inner, mid, rhs, rotations);
+            lhs.rebalance(rotations);
+            lhs
+        } else if rhs_height > lhs_height + 1 {
+            let mut rhs = rhs;
+            let node = rhs
+                .as_mut()
+                .expect("The rhs is required not to be Option::None because it is taller.");
+            let inner = mem::replace(&mut *node.lhs, NodeOption::new());
+            *node.lhs = Self::join(lhs, mid, inner, rotations);
+            rhs.rebalance(rotations);
+            rhs
+        } else {
+            *mid.lhs = lhs;
+            *mid.rhs = rhs;
+            mid.update();
+            Self::from_node(mid)
+        }
+    }
+
+    // Joins two trees, the values of `lhs` are required to be less than those of `rhs`.
+    fn concat(lhs: Self, rhs: Self, rotations: &mut usize) -> Self {
+        let mut lhs = lhs;
+        match lhs.remove_last(rotations) {
+            Some(mid) => Self::join(lhs, mid, rhs, rotations),
+            None => rhs,
+        }
+    }
+
+    // Splits the tree into the values less than the given value, the node equal to it and
+    // the values greater than it.
+    fn split<Q, F>(self, value: &Q, key: F, rotations: &mut usize) -> (Self, Option<Node<T>>, Self)
+    where
+        Q: Ord + ?Sized,
+        F: Fn(&T) -> &Q + Copy,
+    {
+        let Some(node) = self.node_opt else {
+            return (Self::new(), None, Self::new());
+        };
+        let (lhs, node, rhs) = node.into_parts();
+
+        match value.cmp(key(&node.value)) {
+            Ordering::Less => {
+                let (less, mid, greater) = lhs.split(value, key, rotations);
+                (less, mid, Self::join(greater, node, rhs, rotations))
+            }
+            Ordering::Greater => {
+                let (less, mid, greater) = rhs.split(value, key, rotations);
+                (Self::join(lhs, node, less, rotations), mid, greater)
+            }
+            Ordering::Equal => (lhs, Some(node), rhs),
+        }
+    }
+
+    // Removes the node of the greatest value, whose children are Option::None.
+    fn remove_last(&mut self, rotations: &mut usize) -> Option<Node<T>> {
+        let node = self.as_mut()?;
+
+        if (*node.rhs).as_ref().is_some() {
+            let result = node.rhs.remove_last(rotations);
+            self.rebalance(rotations);
+            result
+        } else {
+            let mut node = self
+                .take()
+                .expect("The node is required not to be Option::None.");
+            *self = mem::replace(&mut *node.lhs, NodeOption::new());
+            node.update();
+            Some(node)
+        }
+    }
+
+    // The copies of the values of `other` are added to those of `self` if `multiset` is
+    // true, and dropped otherwise.
+    fn union(self, other: Self, multiset: bool, rotations: &mut usize) -> Self {
+        let Some(node) = self.node_opt else {
+            return other;
+        };
+        let (lhs, mut node, rhs) = node.into_parts();
+        let (less, mid, greater) = other.split(&node.value, |value| value, rotations);
+
+        if let Some(mid) = mid {
+            if multiset {
+                node.duplicates.push_back(mid.value);
+                node.duplicates.extend(mid.duplicates);
+            }
+        }
+
+        Self::join(
+            lhs.union(less, multiset, rotations),
+            node,
+            rhs.union(greater, multiset, rotations),
+            rotations,
+        )
+    }
+
+    fn intersection(self, other: Self, rotations: &mut usize) -> Self {
+        let Some(node) = self.node_opt else {
+            return Self::new();
+        };
+        let (lhs, node, rhs) = node.into_parts();
+        let (less, mid, greater) = other.split(&node.value, |value| value, rotations);
+        let lhs = lhs.intersection(less, rotations);
+        let rhs = rhs.intersection(greater, rotations);
+
+        match mid {
+            Some(_) => Self::join(lhs, node, rhs, rotations),
+            None => Self::concat(lhs, rhs, rotations),
+        }
+    }
+
+    fn difference(self, other: Self, rotations: &mut usize) -> Self {
+        let Some(node) = self.node_opt else {
+            return Self::new();
+        };
+        let (lhs, node, rhs) = node.into_parts();
+        let (less, mid, greater) = other.split(&node.value, |value| value, rotations);
+        let lhs = lhs.difference(less, rotations);
+        let rhs = rhs.difference(greater, rotations);
+
+        match mid {
+            Some(_) => Self::concat(lhs, rhs, rotations),
+            None => Self::join(lhs, node, rhs, rotations),
+        }
+    }
+
     // Returns the height of the given node.
     // If the node is None, then 0 is returned.
-    // If the node is Some, then adding one to the bigger one of the children's heights.
+    // If the node is Some, then the cached height of the node is returned.
     fn get_height(&self) -> i32 {
         match self.as_ref() {
-            Some(node) => cmp::max(node.lhs.get_height(), node.rhs.get_height()) + 1,
+            Some(node) => node.height,
+            None => 0,
+        }
+    }
+
+    // Returns the number of nodes in the given tree.
+    fn get_size(&self) -> usize {
+        match self.as_ref() {
+            Some(node) => node.size,
             None => 0,
         }
     }
@@ -265,11 +1044,41 @@ impl<T: Ord> Node<T> {
     fn new(value: T) -> Self {
         Self {
             value,
+            duplicates: VecDeque::new(),
+            height: 1,
+            size: 1,
             lhs: Box::new(NodeOption::new()),
             rhs: Box::new(NodeOption::new()),
         }
     }
 
+    // Recomputes the height and the size from those of the children.
+    // The height is the bigger one of the children's heights plus one.
+    fn update(&mut self) {
+        self.height = cmp::max(self.lhs.get_height(), self.rhs.get_height()) + 1;
+        self.size = self.lhs.get_size() + self.rhs.get_size() + self.count();
+    }
+
+    // Returns the number of copies of the value in the node.
+    fn count(&self) -> usize {
+        self.duplicates.len() + 1
+    }
+
+    // Returns the `i`-th copy of the value in order of addition.
+    fn get(&self, i: usize) -> &T {
+        match i {
+            0 => &self.value,
+            _ => &self.duplicates[i - 1],
+        }
+    }
+
+    fn get_mut(&mut self, i: usize) -> &mut T {
+        match i {
+            0 => &mut self.value,
+            _ => &mut self.duplicates[i - 1],
+        }
+    }
+
     // Returns the difference of the children's heights.
     // If the right child is taller than left, then the return value is positive.
     // If the left child is taller than right, then the return value is negative.
@@ -282,12 +1091,13 @@ impl<T: Ord> Node<T> {
         }
     }
 
-    // Returns the mutable reference to the node containing the greatest value in the tree.
-    fn max_mut(&mut self) -> &mut Self {
-        match self.rhs.node_opt {
-            Some(ref mut rhs) => rhs.max_mut(),
-            None => self,
-        }
+    // Detaches the children from the node, and returns `(left, node, right)`.
+    fn into_parts(mut self) -> (NodeOption<T>, Self, NodeOption<T>) {
+        let lhs = mem::replace(&mut *self.lhs, NodeOption::new());
+        let rhs = mem::replace(&mut *self.rhs, NodeOption::new());
+        self.update();
+
+        (lhs, self, rhs)
     }
 }
 
@@ -295,8 +1105,12 @@ impl<T: Ord> Node<T> {
 mod tests {
     use super::Balance;
     use super::NodeOption;
+    use super::Range;
     use super::AVL;
+    use std::cmp;
     use std::cmp::Ordering;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::ops::Bound;
 
     const COMPLEX_TREE_SOURCE: [i32; 9] = [7, 5, 4, 2, 6, 11, 9, 10, 13];
 
@@ -313,6 +1127,18 @@ mod tests {
         true
     }
 
+    fn is_valid_cache<T: Ord>(node_opt: &NodeOption<T>) -> bool {
+        match node_opt.as_ref() {
+            Some(node) => {
+                is_valid_cache(&node.lhs)
+                    && is_valid_cache(&node.rhs)
+                    && node.height == cmp::max(node.lhs.get_height(), node.rhs.get_height()) + 1
+                    && node.size == node.lhs.get_size() + node.rhs.get_size() + node.count()
+            }
+            None => true,
+        }
+    }
+
     fn is_valid_structure<T: Ord>(node_opt: &NodeOption<T>) -> bool {
         if let Some(node) = node_opt.as_ref() {
             if let Some(ref lhs) = node.lhs.node_opt {
@@ -336,6 +1162,7 @@ mod tests {
 
         assert!(is_valid_balance(&binary_tree.root));
         assert!(is_valid_structure(&binary_tree.root));
+        assert!(is_valid_cache(&binary_tree.root));
         assert_eq!(binary_tree.find(&3), None);
 
         nums.iter().for_each(|n| {
@@ -356,6 +1183,7 @@ mod tests {
 
             assert!(is_valid_balance(&binary_tree.root));
             assert!(is_valid_structure(&binary_tree.root));
+            assert!(is_valid_cache(&binary_tree.root));
 
             nums.iter().for_each(|n| match n.cmp(removed_num) {
                 Ordering::Equal => assert_eq!(binary_tree.find(removed_num), None),
@@ -364,6 +1192,510 @@ mod tests {
         }
     }
 
+    #[test]
+    fn iter_in_order() {
+        let avl = AVL::from_slice(&COMPLEX_TREE_SOURCE);
+
+        let mut sorted = COMPLEX_TREE_SOURCE.to_vec();
+        sorted.sort();
+        assert_eq!(avl.len(), sorted.len());
+        assert_eq!(avl.iter().copied().collect::<Vec<_>>(), sorted);
+        assert_eq!(avl.iter().len(), sorted.len());
+        assert_eq!(
+            avl.iter().rev().copied().collect::<Vec<_>>(),
+            sorted.iter().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(avl.into_iter().collect::<Vec<_>>(), sorted);
+    }
+
+    #[test]
+    fn iter_from_both_ends() {
+        let avl = AVL::from_slice(&COMPLEX_TREE_SOURCE);
+        let mut iter = avl.iter();
+
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&13));
+        assert_eq!(iter.next_back(), Some(&11));
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.copied().collect::<Vec<_>>(), vec![4, 5, 6, 7, 9, 10]);
+    }
+
+    #[test]
+    fn range() {
+        let avl = AVL::from_slice(&COMPLEX_TREE_SOURCE);
+        let collect = |range: Range<'_, i32>| range.copied().collect::<Vec<_>>();
+
+        assert_eq!(collect(avl.range(5..10)), vec![5, 6, 7, 9]);
+        assert_eq!(collect(avl.range(3..=9)), vec![4, 5, 6, 7, 9]);
+        assert_eq!(collect(avl.range(..6)), vec![2, 4, 5]);
+        assert_eq!(collect(avl.range(10..)), vec![10, 11, 13]);
+        assert_eq!(collect(avl.range(8..9)), vec![]);
+        assert_eq!(collect(avl.range(14..)), vec![]);
+        assert_eq!(
+            avl.range((Bound::Excluded(4), Bound::Excluded(11)))
+                .rev()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![10, 9, 7, 6, 5]
+        );
+    }
+
+    #[test]
+    fn first_and_last() {
+        let mut avl = AVL::from_slice(&COMPLEX_TREE_SOURCE);
+        assert_eq!(avl.first(), Some(&2));
+        assert_eq!(avl.last(), Some(&13));
+
+        avl.remove(&2);
+        avl.remove(&13);
+        assert_eq!(avl.first(), Some(&4));
+        assert_eq!(avl.last(), Some(&11));
+        assert_eq!(avl.len(), 7);
+
+        let empty: AVL<i32> = AVL::new();
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn height_of_sequential_inserts() {
+        let n = 1 << 16;
+        let mut avl = AVL::new();
+        for i in 0..n {
+            avl.add(i);
+        }
+
+        assert!(is_valid_balance(&avl.root));
+        assert!(is_valid_cache(&avl.root));
+        assert_eq!(avl.root.get_height(), 17);
+    }
+
+    #[test]
+    fn nth_and_rank() {
+        let mut avl = AVL::from_slice(&COMPLEX_TREE_SOURCE);
+        let mut sorted = COMPLEX_TREE_SOURCE.to_vec();
+        sorted.sort();
+
+        for (k, v) in sorted.iter().enumerate() {
+            assert_eq!(avl.nth(k), Some(v));
+            assert_eq!(avl.rank(v), k);
+        }
+        assert_eq!(avl.nth(sorted.len()), None);
+        assert_eq!(avl.rank(&0), 0);
+        assert_eq!(avl.rank(&8), 5);
+        assert_eq!(avl.rank(&100), sorted.len());
+
+        avl.remove(&7);
+        assert!(is_valid_cache(&avl.root));
+        assert_eq!(avl.nth(4), Some(&9));
+        assert_eq!(avl.rank(&9), 4);
+    }
+
+    #[test]
+    fn multiset_counts_copies() {
+        let mut avl = AVL::new_multiset();
+        for v in [5, 3, 5, 8, 3, 5, 1] {
+            avl.add(v);
+        }
+
+        assert!(is_valid_balance(&avl.root));
+        assert!(is_valid_cache(&avl.root));
+        assert_eq!(avl.len(), 7);
+        assert_eq!(avl.count(&5), 3);
+        assert_eq!(avl.count(&4), 0);
+        assert_eq!(
+            avl.iter().copied().collect::<Vec<_>>(),
+            vec![1, 3, 3, 5, 5, 5, 8]
+        );
+        assert_eq!(
+            avl.iter().rev().copied().collect::<Vec<_>>(),
+            vec![8, 5, 5, 5, 3, 3, 1]
+        );
+        assert_eq!(avl.range(3..=5).count(), 5);
+        assert_eq!(avl.nth(4), Some(&5));
+        assert_eq!(avl.rank(&8), 6);
+
+        assert_eq!(avl.remove_one(&5), Some(5));
+        assert_eq!(avl.count(&5), 2);
+        assert_eq!(avl.remove_all(&3), vec![3, 3]);
+        assert_eq!(avl.remove_all(&3), vec![]);
+        assert_eq!(avl.remove_one(&1), Some(1));
+        assert_eq!(avl.remove_one(&1), None);
+        assert!(is_valid_cache(&avl.root));
+        assert_eq!(avl.into_iter().collect::<Vec<_>>(), vec![5, 5, 8]);
+    }
+
+    #[test]
+    fn multiset_keeps_order_of_addition() {
+        // Events are ordered only by their priorities.
+        #[derive(Debug)]
+        struct Event(i32, &'static str);
+        impl PartialEq for Event {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Event {}
+        impl PartialOrd for Event {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Event {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut avl = AVL::new_multiset();
+        for event in [Event(2, "a"), Event(1, "b"), Event(2, "c"), Event(2, "d")] {
+            avl.add(event);
+        }
+
+        let names = |avl: &AVL<Event>| avl.iter().map(|e| e.1).collect::<Vec<_>>();
+        assert_eq!(names(&avl), vec!["b", "a", "c", "d"]);
+        assert_eq!(avl.last().map(|e| e.1), Some("d"));
+
+        let mut iter = avl.iter();
+        assert_eq!(iter.next_back().map(|e| e.1), Some("d"));
+        assert_eq!(iter.next().map(|e| e.1), Some("b"));
+        assert_eq!(iter.next().map(|e| e.1), Some("a"));
+        assert_eq!(iter.next_back().map(|e| e.1), Some("c"));
+        assert_eq!(iter.next().map(|e| e.1), None);
+
+        assert_eq!(avl.remove_one(&Event(2, "")).map(|e| e.1), Some("a"));
+        assert_eq!(names(&avl), vec!["b", "c", "d"]);
+    }
+
+    #[test]
+    fn set_drops_duplicates() {
+        let mut avl = AVL::new();
+        avl.add(3);
+        avl.add(3);
+
+        assert!(!avl.is_multiset());
+        assert_eq!(avl.count(&3), 1);
+        assert_eq!(avl.len(), 1);
+        assert_eq!(avl.remove_all(&3), vec![3]);
+    }
+
+    fn is_valid<T: Ord>(avl: &AVL<T>) -> bool {
+        is_valid_balance(&avl.root) && is_valid_cache(&avl.root) && is_valid_structure(&avl.root)
+    }
+
+    // Returns `n` pseudo-random numbers less than `max`.
+    fn random_values(n: usize, max: u64, seed: u64) -> Vec<u64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) % max
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_and_join() {
+        let avl = AVL::from_slice(&COMPLEX_TREE_SOURCE);
+
+        let (lhs, rhs) = avl.split(&9);
+        assert!(is_valid(&lhs) && is_valid(&rhs));
+        assert_eq!(lhs.iter().copied().collect::<Vec<_>>(), vec![2, 4, 5, 6, 7]);
+        assert_eq!(rhs.iter().copied().collect::<Vec<_>>(), vec![9, 10, 11, 13]);
+
+        let avl = AVL::join(lhs, rhs);
+        assert!(is_valid(&avl));
+        assert_eq!(avl.len(), COMPLEX_TREE_SOURCE.len());
+
+        let (lhs, rhs) = avl.split(&8);
+        assert_eq!(lhs.len(), 5);
+        assert_eq!(rhs.len(), 4);
+        let (empty, all) = AVL::join(lhs, rhs).split(&0);
+        assert!(empty.is_empty());
+        assert_eq!(all.len(), COMPLEX_TREE_SOURCE.len());
+    }
+
+    #[test]
+    fn split_and_join_trees_of_different_heights() {
+        for n in [0, 1, 2, 10, 100, 1000] {
+            for m in [0, 1, 3, 50, 2000] {
+                let lhs = AVL::from_slice(&(0..n).collect::<Vec<_>>());
+                let rhs = AVL::from_slice(&(n..n + m).collect::<Vec<_>>());
+
+                let avl = AVL::join(lhs, rhs);
+                assert!(is_valid(&avl));
+                assert!(avl.iter().copied().eq(0..n + m));
+
+                for k in [0, n / 2, n, n + m] {
+                    let avl = AVL::from_slice(&(0..n + m).collect::<Vec<_>>());
+                    let (lhs, rhs) = avl.split(&k);
+                    assert!(is_valid(&lhs) && is_valid(&rhs));
+                    assert!(lhs.iter().copied().eq(0..k));
+                    assert!(rhs.iter().copied().eq(k..n + m));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_sorted_iter() {
+        for n in 0..100usize {
+            let avl = AVL::from_sorted_iter(0..n);
+
+            assert!(is_valid(&avl));
+            assert!(avl.iter().copied().eq(0..n));
+            // A perfectly balanced tree of n values has the minimum height.
+            assert_eq!(
+                avl.root.get_height(),
+                (n + 1).next_power_of_two().trailing_zeros() as i32
+            );
+            assert_eq!(avl.rotation_count(), 0);
+        }
+
+        let avl = AVL::from_sorted_iter([1, 1, 2, 3, 3, 3]);
+        assert!(is_valid(&avl));
+        assert!(avl.into_iter().eq([1, 2, 3]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_unsorted_iter() {
+        AVL::from_sorted_iter([1, 3, 2]);
+    }
+
+    #[test]
+    fn from_values_without_copy() {
+        let words = ["pear", "apple", "fig"].map(String::from);
+        let mut avl = AVL::from_slice(&words);
+        avl.extend(vec![String::from("kiwi")]);
+
+        assert_eq!(avl.len(), 4);
+        assert_eq!(avl.first().map(String::as_str), Some("apple"));
+
+        let avl = AVL::from_sorted_iter(avl);
+        assert!(is_valid(&avl));
+        assert!(avl.iter().eq(["apple", "fig", "kiwi", "pear"].iter()));
+    }
+
+    #[test]
+    #[should_panic(expected = "The trees are required to be both sets or both multisets.")]
+    fn join_set_and_multiset() {
+        let mut right = AVL::new_multiset();
+        right.add(3);
+        right.add(3);
+
+        AVL::join(AVL::from_slice(&[1, 2]), right);
+    }
+
+    #[test]
+    #[should_panic(expected = "The trees are required to be both sets or both multisets.")]
+    fn union_set_and_multiset() {
+        AVL::from_slice(&[1, 2]).union(AVL::new_multiset());
+    }
+
+    #[test]
+    #[should_panic]
+    fn join_overlapping_trees() {
+        AVL::join(AVL::from_slice(&[1, 5]), AVL::from_slice(&[3, 7]));
+    }
+
+    #[test]
+    fn append() {
+        let mut avl = AVL::from_slice(&[5, 6, 7]);
+        let mut other = AVL::from_slice(&[1, 2]);
+        avl.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(avl.iter().copied().collect::<Vec<_>>(), vec![1, 2, 5, 6, 7]);
+
+        let mut other = AVL::from_slice(&[9, 3, 5]);
+        avl.append(&mut other);
+        assert!(is_valid(&avl));
+        assert_eq!(
+            avl.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 5, 6, 7, 9]
+        );
+    }
+
+    #[test]
+    fn set_operations() {
+        for seed in 0..20 {
+            let xs = random_values(300, 500, seed);
+            let ys = random_values(200, 500, seed + 100);
+            let x_set: BTreeSet<_> = xs.iter().copied().collect();
+            let y_set: BTreeSet<_> = ys.iter().copied().collect();
+
+            let union = AVL::from_slice(&xs).union(AVL::from_slice(&ys));
+            let intersection = AVL::from_slice(&xs).intersection(AVL::from_slice(&ys));
+            let difference = AVL::from_slice(&xs).difference(AVL::from_slice(&ys));
+
+            assert!(is_valid(&union) && is_valid(&intersection) && is_valid(&difference));
+            assert!(union.iter().eq(x_set.union(&y_set)));
+            assert!(intersection.iter().eq(x_set.intersection(&y_set)));
+            assert!(difference.iter().eq(x_set.difference(&y_set)));
+        }
+    }
+
+    #[test]
+    fn set_operations_on_multisets() {
+        let multiset = |values: &[i32]| {
+            let mut avl = AVL::new_multiset();
+            values.iter().for_each(|v| avl.add(*v));
+            avl
+        };
+
+        let union = multiset(&[1, 2, 2, 3]).union(multiset(&[2, 3, 4]));
+        assert_eq!(
+            union.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 2, 2, 3, 3, 4]
+        );
+        let intersection = multiset(&[1, 2, 2, 3]).intersection(multiset(&[2, 4]));
+        assert_eq!(intersection.iter().copied().collect::<Vec<_>>(), vec![2, 2]);
+        let difference = multiset(&[1, 2, 2, 3]).difference(multiset(&[2, 4]));
+        assert_eq!(difference.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+
+        let (lhs, rhs) = multiset(&[1, 2, 2, 3]).split(&2);
+        assert!(lhs.is_multiset() && rhs.is_multiset());
+        assert_eq!(rhs.count(&2), 2);
+        assert_eq!(lhs.len() + rhs.len(), 4);
+    }
+
+    #[test]
+    fn double_rotations() {
+        // Left-right and right-left cases on insertion.
+        for source in [[3, 1, 2], [1, 3, 2]] {
+            let avl = AVL::from_slice(&source);
+            assert!(is_valid(&avl));
+            assert_eq!(avl.root.as_ref().map(|node| node.value), Some(2));
+        }
+
+        // Left-right and right-left cases on removal.
+        for (source, removed) in [([5, 2, 6, 4], 6), ([3, 2, 6, 4], 2)] {
+            let mut avl = AVL::from_slice(&source);
+            avl.remove(&removed);
+            assert!(is_valid(&avl));
+            assert_eq!(avl.root.as_ref().map(|node| node.value), Some(4));
+        }
+    }
+
+    // Applies random insertions and removals to an AVL and a BTreeSet, comparing them.
+    fn test_random_operations(operation_count: usize, max: u64, seed: u64) {
+        let mut avl = AVL::new();
+        let mut expected = BTreeSet::new();
+
+        let values = random_values(2 * operation_count, max, seed);
+        for (i, ops) in values.chunks(2).enumerate() {
+            let (op, value) = (ops[0], ops[1]);
+            // Inserts slightly more often than removes, so that the tree keeps growing.
+            if op % 5 < 3 {
+                avl.add(value);
+                expected.insert(value);
+            } else {
+                assert_eq!(avl.remove(&value), expected.take(&value));
+            }
+            assert_eq!(avl.find(&value), expected.get(&value));
+            assert_eq!(avl.len(), expected.len());
+
+            if i % 50_000 == 0 {
+                assert!(is_valid(&avl));
+                assert!(avl.iter().eq(expected.iter()));
+            }
+        }
+
+        assert!(is_valid(&avl));
+        assert!(avl.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn random_operations_on_small_keys() {
+        for seed in 0..1_000 {
+            test_random_operations(200, 20, seed);
+        }
+    }
+
+    #[test]
+    fn random_operations_on_large_keys() {
+        for seed in 0..2 {
+            test_random_operations(100_000, 100_000, seed);
+        }
+    }
+
+    // Runs millions of operations, which is slow without optimizations.
+    // Run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn random_operations_stress() {
+        for seed in 0..4 {
+            test_random_operations(1_000_000, 200_000, seed);
+        }
+    }
+
+    #[test]
+    fn random_operations_on_multiset() {
+        let mut avl = AVL::new_multiset();
+        let mut counts = BTreeMap::new();
+
+        let values = random_values(400_000, 1_000, 7);
+        for (i, ops) in values.chunks(2).enumerate() {
+            let (op, value) = (ops[0], ops[1]);
+            match op % 6 {
+                0..=3 => {
+                    avl.add(value);
+                    *counts.entry(value).or_insert(0) += 1;
+                }
+                4 => {
+                    let removed = avl.remove_one(&value);
+                    match counts.get_mut(&value) {
+                        Some(count) => {
+                            assert_eq!(removed, Some(value));
+                            *count -= 1;
+                            if *count == 0 {
+                                counts.remove(&value);
+                            }
+                        }
+                        None => assert_eq!(removed, None),
+                    }
+                }
+                _ => {
+                    let count = counts.remove(&value).unwrap_or(0);
+                    assert_eq!(avl.remove_all(&value), vec![value; count]);
+                }
+            }
+            assert_eq!(avl.count(&value), counts.get(&value).copied().unwrap_or(0));
+
+            if i % 10_000 == 0 {
+                assert!(is_valid(&avl));
+                assert_eq!(avl.len(), counts.values().sum());
+            }
+        }
+
+        assert!(is_valid(&avl));
+        assert!(avl
+            .iter()
+            .copied()
+            .eq(counts.iter().flat_map(|(v, c)| vec![*v; *c])));
+    }
+
+    #[test]
+    fn random_split_and_join() {
+        for seed in 0..200 {
+            let values = random_values(500, 1_000, seed);
+            let key = values[0];
+            let expected: BTreeSet<_> = values.iter().copied().collect();
+
+            let (lhs, rhs) = AVL::from_slice(&values).split(&key);
+            assert!(is_valid(&lhs) && is_valid(&rhs));
+            assert!(lhs.iter().eq(expected.range(..key)));
+            assert!(rhs.iter().eq(expected.range(key..)));
+
+            let avl = AVL::join(lhs, rhs);
+            assert!(is_valid(&avl));
+            assert!(avl.iter().eq(expected.iter()));
+        }
+    }
+
     #[test]
     fn find_empty_tree() {
         let binary_tree = AVL::new();
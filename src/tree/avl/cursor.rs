@@ -0,0 +1,108 @@
+use super::super::cursor::{PathCursor, RankCursor};
+use super::{Node, AVL};
+
+/// A cursor over the values in an `AVL`, which moves in both directions.
+///
+/// The cursor points at one of the values in ascending order, or at the ghost position,
+/// which lies after the greatest value and before the smallest one. The cursor keeps the
+/// path from the root, so that each move takes O(1) amortized time.
+///
+/// This struct is created by `AVL::lower_bound` and `AVL::upper_bound`.
+pub struct Cursor<'a, T: Ord> {
+    inner: PathCursor<'a, Node<T>>,
+}
+
+/// A cursor over the values in an `AVL`, which can also remove the value pointed at.
+///
+/// The cursor keeps the rank of the value pointed at, so that each move takes O(log n)
+/// time.
+///
+/// This struct is created by `AVL::lower_bound_mut` and `AVL::upper_bound_mut`.
+pub struct CursorMut<'a, T: Ord> {
+    inner: RankCursor<'a, AVL<T>>,
+}
+
+impl<'a, T: Ord> Cursor<'a, T> {
+    pub(super) fn lower_bound(avl: &'a AVL<T>, value: &T) -> Self {
+        Self {
+            inner: PathCursor::lower_bound(avl, value),
+        }
+    }
+
+    pub(super) fn upper_bound(avl: &'a AVL<T>, value: &T) -> Self {
+        Self {
+            inner: PathCursor::upper_bound(avl, value),
+        }
+    }
+
+    /// Returns the value pointed at by the cursor.
+    ///
+    /// If the cursor points at the ghost position, then Option::None is returned.
+    pub fn peek(&self) -> Option<&'a T> {
+        self.inner.peek()
+    }
+
+    /// Moves the cursor to the next value, and returns it.
+    ///
+    /// The cursor moves from the greatest value to the ghost position, and from the ghost
+    /// position to the smallest value.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    /// Moves the cursor to the previous value, and returns it.
+    ///
+    /// The cursor moves from the smallest value to the ghost position, and from the ghost
+    /// position to the greatest value.
+    pub fn prev(&mut self) -> Option<&'a T> {
+        self.inner.prev()
+    }
+}
+
+impl<'a, T: Ord> CursorMut<'a, T> {
+    pub(super) fn lower_bound(avl: &'a mut AVL<T>, value: &T) -> Self {
+        Self {
+            inner: RankCursor::lower_bound(avl, value),
+        }
+    }
+
+    pub(super) fn upper_bound(avl: &'a mut AVL<T>, value: &T) -> Self {
+        Self {
+            inner: RankCursor::upper_bound(avl, value),
+        }
+    }
+
+    /// Returns the value pointed at by the cursor.
+    ///
+    /// If the cursor points at the ghost position, then Option::None is returned.
+    pub fn peek(&self) -> Option<&T> {
+        self.inner.peek()
+    }
+
+    /// Moves the cursor to the next value, and returns it.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&T> {
+        self.inner.next()
+    }
+
+    /// Moves the cursor to the previous value, and returns it.
+    pub fn prev(&mut self) -> Option<&T> {
+        self.inner.prev()
+    }
+
+    /// Removes the value pointed at by the cursor, and returns it.
+    ///
+    /// The cursor then points at the next value. If the cursor points at the ghost
+    /// position, then nothing is removed and Option::None is returned.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.inner.remove_current()
+    }
+
+    /// Returns a read-only cursor pointing at the same value.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            inner: self.inner.as_path_cursor(),
+        }
+    }
+}
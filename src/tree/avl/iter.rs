@@ -0,0 +1,248 @@
+use std::iter::FusedIterator;
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
+use std::vec;
+
+use super::{Node, NodeOption, AVL};
+use crate::tree::iter::{into_sorted_vec, InOrder, OwnedNode};
+
+/// An iterator over a sub-range of the values in an `AVL`, in ascending order.
+///
+/// This struct is created by `AVL::range`.
+pub struct Range<'a, T: Ord> {
+    // The top of `front` is the smallest value not yet yielded from the front, and the top
+    // of `back` is the greatest value not yet yielded from the back.
+    front: Vec<&'a Node<T>>,
+    back: Vec<&'a Node<T>>,
+    // The numbers of copies already yielded from the tops of `front` and `back`.
+    front_yielded: usize,
+    back_yielded: usize,
+}
+
+/// An iterator over the values in an `AVL`, in ascending order.
+///
+/// This struct is created by `AVL::iter`.
+pub struct Iter<'a, T: Ord> {
+    inner: InOrder<'a, Node<T>>,
+}
+
+/// An owning iterator over the values in an `AVL`, in ascending order.
+///
+/// This struct is created by `AVL::into_iter`.
+pub struct IntoIter<T> {
+    values: vec::IntoIter<T>,
+}
+
+impl<'a, T: Ord> Range<'a, T> {
+    // The values are compared with the bounds through `key`, which is required to preserve
+    // the order of the values.
+    pub(super) fn new<Q, R, F>(root: &'a NodeOption<T>, range: R, key: F) -> Self
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        F: Fn(&T) -> &Q,
+    {
+        let mut front = Vec::new();
+        let mut node_opt = root;
+        while let Some(node) = node_opt.as_ref() {
+            let is_after_start = match range.start_bound() {
+                Bound::Included(start) => key(&node.value) >= start,
+                Bound::Excluded(start) => key(&node.value) > start,
+                Bound::Unbounded => true,
+            };
+
+            if is_after_start {
+                front.push(node);
+                node_opt = &node.lhs;
+            } else {
+                node_opt = &node.rhs;
+            }
+        }
+
+        let mut back = Vec::new();
+        let mut node_opt = root;
+        while let Some(node) = node_opt.as_ref() {
+            let is_before_end = match range.end_bound() {
+                Bound::Included(end) => key(&node.value) <= end,
+                Bound::Excluded(end) => key(&node.value) < end,
+                Bound::Unbounded => true,
+            };
+
+            if is_before_end {
+                back.push(node);
+                node_opt = &node.rhs;
+            } else {
+                node_opt = &node.lhs;
+            }
+        }
+
+        Self {
+            front,
+            back,
+            front_yielded: 0,
+            back_yielded: 0,
+        }
+    }
+
+    // The values not yet yielded are those between the tops of the stacks.
+    fn is_exhausted(&self) -> bool {
+        match (self.front.last(), self.back.last()) {
+            (Some(front), Some(back)) if ptr::eq(*front, *back) => {
+                self.front_yielded + self.back_yielded >= front.count()
+            }
+            (Some(front), Some(back)) => front.value > back.value,
+            _ => true,
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_exhausted() {
+            return None;
+        }
+
+        let node = *self.front.last()?;
+        let value = node.get(self.front_yielded);
+        self.front_yielded += 1;
+
+        if self.front_yielded == node.count() {
+            self.front.pop();
+            self.front_yielded = 0;
+
+            let mut node_opt = &*node.rhs;
+            while let Some(n) = node_opt.as_ref() {
+                self.front.push(n);
+                node_opt = &n.lhs;
+            }
+        }
+
+        Some(value)
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for Range<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.is_exhausted() {
+            return None;
+        }
+
+        let node = *self.back.last()?;
+        self.back_yielded += 1;
+        let value = node.get(node.count() - self.back_yielded);
+
+        if self.back_yielded == node.count() {
+            self.back.pop();
+            self.back_yielded = 0;
+
+            let mut node_opt = &*node.lhs;
+            while let Some(n) = node_opt.as_ref() {
+                self.back.push(n);
+                node_opt = &n.rhs;
+            }
+        }
+
+        Some(value)
+    }
+}
+
+impl<T: Ord> FusedIterator for Range<'_, T> {}
+
+impl<'a, T: Ord> Iter<'a, T> {
+    pub(super) fn new(avl: &'a AVL<T>) -> Self {
+        Self {
+            inner: InOrder::new(avl.root.as_ref(), avl.len()),
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: Ord> FusedIterator for Iter<'_, T> {}
+
+impl<T: Ord> IntoIter<T> {
+    pub(super) fn new(avl: AVL<T>) -> Self {
+        let len = avl.len();
+        let mut root = avl.root;
+        Self {
+            values: into_sorted_vec(root.take(), len).into_iter(),
+        }
+    }
+}
+
+impl<T: Ord> OwnedNode for Node<T> {
+    type Value = T;
+
+    fn take_lhs(&mut self) -> Option<Self> {
+        self.lhs.take()
+    }
+
+    fn take_rhs(&mut self) -> Option<Self> {
+        self.rhs.take()
+    }
+
+    fn move_values(self, values: &mut Vec<T>) {
+        values.push(self.value);
+        values.extend(self.duplicates);
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.values.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<'a, T: Ord> IntoIterator for &'a AVL<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> IntoIterator for AVL<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
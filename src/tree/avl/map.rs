@@ -0,0 +1,691 @@
+//! An ordered map built on `AVL`.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::mem;
+use std::ops::RangeBounds;
+
+use super::AVL;
+
+/// An ordered map based on an AVL tree.
+///
+/// The entries are kept in an `AVL` ordered by their keys, so that the map shares the
+/// balancing logic of the set. The API mirrors that of `std::collections::BTreeMap`.
+pub struct AvlMap<K: Ord, V> {
+    tree: AVL<MapEntry<K, V>>,
+}
+
+// An entry of the map, which is compared only by its key.
+struct MapEntry<K, V> {
+    key: K,
+    value: V,
+}
+
+/// A view into a single entry in a map, which may either be vacant or occupied.
+///
+/// This enum is created by `AvlMap::entry`.
+pub enum Entry<'a, K: Ord, V> {
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+/// A view into a vacant entry in an `AvlMap`.
+pub struct VacantEntry<'a, K: Ord, V> {
+    map: &'a mut AvlMap<K, V>,
+    key: K,
+    // The rank at which the entry is inserted.
+    rank: usize,
+}
+
+/// A view into an occupied entry in an `AvlMap`.
+pub struct OccupiedEntry<'a, K: Ord, V> {
+    map: &'a mut AvlMap<K, V>,
+    // The rank of the entry, which is followed instead of searching for the key again.
+    rank: usize,
+}
+
+/// An iterator over the entries of an `AvlMap`, in ascending order of keys.
+pub struct Iter<'a, K: Ord, V> {
+    inner: super::Iter<'a, MapEntry<K, V>>,
+}
+
+/// An iterator over a sub-range of the entries of an `AvlMap`.
+pub struct Range<'a, K: Ord, V> {
+    inner: super::Range<'a, MapEntry<K, V>>,
+}
+
+/// An owning iterator over the entries of an `AvlMap`, in ascending order of keys.
+pub struct IntoIter<K, V> {
+    inner: super::IntoIter<MapEntry<K, V>>,
+}
+
+/// An iterator over the keys of an `AvlMap`, in ascending order.
+pub struct Keys<'a, K: Ord, V> {
+    inner: Iter<'a, K, V>,
+}
+
+/// An iterator over the values of an `AvlMap`, in ascending order of keys.
+pub struct Values<'a, K: Ord, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<K: Ord, V> AvlMap<K, V> {
+    /// Constructs a new, empty AvlMap<K, V>.
+    pub fn new() -> Self {
+        Self { tree: AVL::new() }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Returns true if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .root
+            .find(key, MapEntry::key)
+            .map(|node| &node.value.value)
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .root
+            .find(key, MapEntry::key)
+            .map(|node| node.value.pair())
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .root
+            .find_mut(key, MapEntry::key)
+            .map(|entry| &mut entry.value)
+    }
+
+    /// Returns true if the map contains a value for the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree.root.find(key, MapEntry::key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have the key, Option::None is returned. If the map did have the
+    /// key, the value is updated and the old value is returned. The key is not updated.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.get_mut(&key) {
+            Some(old) => Some(mem::replace(old, value)),
+            None => {
+                self.tree.add(MapEntry { key, value });
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if it was in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if it was in the map.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .root
+            .remove(key, MapEntry::key, &mut self.tree.rotations)
+            .map(|node| (node.value.key, node.value.value))
+    }
+
+    /// Gets the entry for the key for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.tree.root.search(&key, MapEntry::key) {
+            Ok(rank) => Entry::Occupied(OccupiedEntry { map: self, rank }),
+            Err(rank) => Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                rank,
+            }),
+        }
+    }
+
+    /// Returns the entry with the smallest key.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.tree.first().map(MapEntry::pair)
+    }
+
+    /// Returns the entry with the greatest key.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.tree.last().map(MapEntry::pair)
+    }
+
+    /// Returns an iterator over the entries in ascending order of keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.tree.iter(),
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys are within a given range.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range {
+            inner: super::Range::new(&self.tree.root, range, MapEntry::key),
+        }
+    }
+
+    /// Returns an iterator over the keys in ascending order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Returns an iterator over the values in ascending order of keys.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    // Returns the entry of a given rank, which is required to be less than the length.
+    fn nth_entry(&self, rank: usize) -> &MapEntry<K, V> {
+        self.tree
+            .nth(rank)
+            .expect("The rank of an entry is required to be less than the length.")
+    }
+
+    fn nth_entry_mut(&mut self, rank: usize) -> &mut MapEntry<K, V> {
+        self.tree
+            .root
+            .nth_mut(rank)
+            .expect("The rank of an entry is required to be less than the length.")
+    }
+}
+
+impl<K: Ord, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Debug for AvlMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for AvlMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a AvlMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V> IntoIterator for AvlMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.tree.into_iter(),
+        }
+    }
+}
+
+impl<K, V> MapEntry<K, V> {
+    fn key<Q>(&self) -> &Q
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+    {
+        self.key.borrow()
+    }
+
+    fn pair(&self) -> (&K, &V) {
+        (&self.key, &self.value)
+    }
+}
+
+impl<K: Ord, V> PartialEq for MapEntry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Ord, V> Eq for MapEntry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for MapEntry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for MapEntry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Vacant(entry) => entry.key(),
+            Entry::Occupied(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns a mutable
+    /// reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => entry.insert(default()),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Inserts the result of `default` called with the key if the entry is vacant, and
+    /// returns a mutable reference to the value.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Inserts the default value if the entry is vacant, and returns a mutable reference to
+    /// the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Modifies the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    /// Returns the key which would be used when inserting a value.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the value with the key of the entry, and returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let map = self.map;
+        map.tree.add(MapEntry {
+            key: self.key,
+            value,
+        });
+
+        // The inserted entry is followed by its rank, since the tree is rebalanced on the
+        // way back from the added node.
+        &mut map.nth_entry_mut(self.rank).value
+    }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.map.nth_entry(self.rank).key
+    }
+
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &V {
+        &self.map.nth_entry(self.rank).value
+    }
+
+    /// Returns a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.nth_entry_mut(self.rank).value
+    }
+
+    /// Converts the entry into a mutable reference to the value with the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.nth_entry_mut(self.rank).value
+    }
+
+    /// Sets the value of the entry, and returns the old value.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map, and returns the value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes the entry from the map, and returns the stored key and value.
+    pub fn remove_entry(self) -> (K, V) {
+        let tree = &mut self.map.tree;
+        let entry = tree
+            .root
+            .remove_nth(self.rank, &mut tree.rotations)
+            .expect("The rank of an entry is required to be less than the length.");
+        (entry.key, entry.value)
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(MapEntry::pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(MapEntry::pair)
+    }
+}
+
+impl<K: Ord, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K: Ord, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(MapEntry::pair)
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for Range<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(MapEntry::pair)
+    }
+}
+
+impl<K: Ord, V> FusedIterator for Range<'_, K, V> {}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (entry.key, entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| (entry.key, entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<'a, K: Ord, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<K: Ord, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<'a, K: Ord, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<K: Ord, V> ExactSizeIterator for Values<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn insert_and_get() {
+        let mut map = AvlMap::new();
+
+        assert_eq!(map.insert(3, "c"), None);
+        assert_eq!(map.insert(1, "a"), None);
+        assert_eq!(map.insert(2, "b"), None);
+        assert_eq!(map.insert(3, "C"), Some("c"));
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&3), Some(&"C"));
+        assert_eq!(map.get(&4), None);
+        assert!(map.contains_key(&1));
+
+        *map.get_mut(&2).unwrap() = "B";
+        assert_eq!(map.get_key_value(&2), Some((&2, &"B")));
+    }
+
+    #[test]
+    fn remove() {
+        let mut map: AvlMap<i32, i32> = (0..10).map(|k| (k, k * k)).collect();
+
+        assert_eq!(map.remove(&3), Some(9));
+        assert_eq!(map.remove(&3), None);
+        assert_eq!(map.remove_entry(&4), Some((4, 16)));
+        assert_eq!(map.len(), 8);
+        assert_eq!(map.first_key_value(), Some((&0, &0)));
+        assert_eq!(map.last_key_value(), Some((&9, &81)));
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut map = AvlMap::new();
+        map.insert(String::from("apple"), 3);
+        map.insert(String::from("banana"), 5);
+
+        assert_eq!(map.get("apple"), Some(&3));
+        assert_eq!(map.remove("banana"), Some(5));
+        assert!(!map.contains_key("banana"));
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = AvlMap::new();
+
+        for word in ["b", "a", "b", "c", "b", "a"] {
+            *map.entry(word).or_insert(0) += 1;
+        }
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(&"a", &2), (&"b", &3), (&"c", &1)]
+        );
+
+        map.entry("a").and_modify(|v| *v *= 10).or_default();
+        map.entry("d").and_modify(|v| *v *= 10).or_default();
+        assert_eq!(map.get("a"), Some(&20));
+        assert_eq!(map.get("d"), Some(&0));
+
+        match map.entry("c") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &"c");
+                assert_eq!(entry.insert(7), 1);
+                assert_eq!(entry.remove(), 7);
+            }
+            Entry::Vacant(_) => panic!("The entry is occupied."),
+        }
+        match map.entry("e") {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key(), &"e");
+                *entry.insert(4) += 1;
+            }
+            Entry::Occupied(_) => panic!("The entry is vacant."),
+        }
+        assert_eq!(map.get("e"), Some(&5));
+        assert!(!map.contains_key("c"));
+        assert_eq!(*map.entry("f").or_insert_with_key(|k| k.len()), 1);
+    }
+
+    #[test]
+    fn entry_follows_its_rank() {
+        let mut map: AvlMap<i32, i32> = (0..100).map(|k| (2 * k, k)).collect();
+
+        for k in 0..100 {
+            *map.entry(2 * k + 1).or_insert(0) += k;
+            *map.entry(2 * k).or_insert(0) += k;
+        }
+        for k in 0..200 {
+            assert_eq!(map.get(&k), Some(&if k % 2 == 0 { k } else { k / 2 }));
+        }
+
+        for k in (0..200).step_by(3) {
+            match map.entry(k) {
+                Entry::Occupied(entry) => {
+                    assert_eq!(entry.key(), &k);
+                    assert_eq!(entry.remove_entry().0, k);
+                }
+                Entry::Vacant(_) => panic!("The entry is occupied."),
+            }
+        }
+        assert!(map.keys().copied().eq((0..200).filter(|k| k % 3 != 0)));
+    }
+
+    #[test]
+    fn iterators() {
+        let map: AvlMap<i32, char> = vec![(3, 'c'), (1, 'a'), (4, 'd'), (2, 'b')]
+            .into_iter()
+            .collect();
+
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            map.values().rev().copied().collect::<Vec<_>>(),
+            vec!['d', 'c', 'b', 'a']
+        );
+        assert_eq!(
+            map.range(2..4).collect::<Vec<_>>(),
+            vec![(&2, &'b'), (&3, &'c')]
+        );
+        assert_eq!(format!("{:?}", map), "{1: 'a', 2: 'b', 3: 'c', 4: 'd'}");
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]
+        );
+    }
+
+    #[test]
+    fn same_as_btree_map() {
+        let mut map = AvlMap::new();
+        let mut expected = BTreeMap::new();
+
+        let mut seed: u64 = 42;
+        for _ in 0..2000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let key = (seed >> 33) % 200;
+            if seed >> 63 == 0 {
+                assert_eq!(map.insert(key, seed), expected.insert(key, seed));
+            } else {
+                assert_eq!(map.remove(&key), expected.remove(&key));
+            }
+        }
+
+        assert!(map.iter().eq(expected.iter()));
+    }
+}
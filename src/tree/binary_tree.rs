@@ -1,14 +1,30 @@
 use std::mem;
 use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::collections::VecDeque;
+
+use super::iter::sorted_dedup;
+use super::OrderedSet;
+
+mod cursor;
+mod iter;
+
+pub use cursor::{Cursor, CursorMut};
+pub use iter::{IntoIter, Iter};
 
 pub struct BinaryTree<T: Ord> {
     root: NodeOpt<T>,
+    // If true then equal values are kept in the same node instead of being dropped.
+    multiset: bool,
 }
 
 #[derive(Debug)]
-struct Node<T: Ord> {
+pub(crate) struct Node<T: Ord> {
     value: T,
+    // The values equal to `value` which were added after it, in order of addition.
+    duplicates: VecDeque<T>,
+    // The number of values of the subtree rooted at the node, which is kept up to date by
+    // `update` whenever the subtree changes.
+    size: usize,
     lhs: NodeOpt<T>,
     rhs: NodeOpt<T>,
 }
@@ -16,131 +32,519 @@ struct Node<T: Ord> {
 type NodeOpt<T> = Option<Box<Node<T>>>;
 
 
-impl<T: Ord + Debug> Node<T> {
+// Returns the number of values of the subtree, which is 0 for None.
+fn get_size<T: Ord>(node_opt: &NodeOpt<T>) -> usize {
+    node_opt.as_ref().map_or(0, |node| node.size)
+}
+
+impl<T: Ord> Node<T> {
     fn new(value: T) -> Self {
-        Self { value, lhs: None, rhs: None }
+        Self { value, duplicates: VecDeque::new(), size: 1, lhs: None, rhs: None }
+    }
+
+    // Builds a tree of the next `len` values of `values`, which are in ascending order.
+    // The sizes of the subtrees of every node differ by at most one.
+    fn from_sorted<I: Iterator<Item = T>>(values: &mut I, len: usize) -> NodeOpt<T> {
+        if len == 0 {
+            return None;
+        }
+
+        let lhs = Self::from_sorted(values, len / 2);
+        let value = values.next().expect("The iterator must have `len` values left.");
+        let rhs = Self::from_sorted(values, len - len / 2 - 1);
+
+        let mut node = Self::new(value);
+        node.lhs = lhs;
+        node.rhs = rhs;
+        node.update();
+        Some(Box::new(node))
+    }
+
+    // Returns the number of copies of the value in the node.
+    fn count(&self) -> usize {
+        self.duplicates.len() + 1
+    }
+
+    fn update(&mut self) {
+        self.size = get_size(&self.lhs) + get_size(&self.rhs) + self.count();
     }
 
+    // The tree is not balanced, so that its height can be as large as its size. The
+    // methods below follow the paths with loops, which do not overflow the stack.
     fn find(&self, value: &T) -> Option<&Self> {
-        match value.cmp(&self.value) {
-            Ordering::Equal => Some(self),
-            Ordering::Less => self.lhs.as_ref()?.find(value),
-            Ordering::Greater => self.rhs.as_ref()?.find(value),
+        let mut node = self;
+        loop {
+            node = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(node),
+                Ordering::Less => node.lhs.as_ref()?,
+                Ordering::Greater => node.rhs.as_ref()?,
+            };
         }
     }
 
-    fn add(&mut self, value: T) {
-        match value.cmp(&self.value) {
-            Ordering::Less => { 
-                match self.lhs.as_mut() {
-                    Some(lhs) => lhs.add(value),
-                    None => { self.lhs = Some(Box::new(Node::new(value))) },
-                }
-            },
-            Ordering::Greater => {
-                match self.rhs.as_mut() {
-                    Some(rhs) => rhs.add(value),
-                    None => { self.rhs = Some(Box::new(Node::new(value))) },
-                }
+    // Follows the path to the node of the value, or to the empty subtree where the value
+    // would be added, and returns it. `update` is applied to every node passed on the way.
+    fn path_to<'a>(
+        node_opt: &'a mut NodeOpt<T>,
+        value: &T,
+        update: impl Fn(&mut Self),
+    ) -> &'a mut NodeOpt<T> {
+        let mut node_opt = node_opt;
+        loop {
+            let ordering = match node_opt.as_ref() {
+                Some(node) => value.cmp(&node.value),
+                None => return node_opt,
+            };
+            if ordering == Ordering::Equal {
+                return node_opt;
+            }
+
+            let node = node_opt.as_mut().expect("The node is required to exist.");
+            update(node);
+            node_opt = match ordering {
+                Ordering::Less => &mut node.lhs,
+                _ => &mut node.rhs,
+            };
+        }
+    }
+
+    // Returns true if the value has been added.
+    fn add(node_opt: &mut NodeOpt<T>, value: T, multiset: bool) -> bool {
+        // The sizes on the path are only increased if the value is to be added.
+        if !multiset && node_opt.as_ref().and_then(|node| node.find(&value)).is_some() {
+            return false;
+        }
+
+        let node_opt = Self::path_to(node_opt, &value, |node| node.size += 1);
+        match node_opt {
+            Some(node) => {
+                node.duplicates.push_back(value);
+                node.size += 1;
             },
-            Ordering::Equal => (),
-        };
+            None => *node_opt = Some(Box::new(Node::new(value))),
+        }
+        true
     }
 
-    fn min_mut(&mut self) -> &mut Self {
-        match self.lhs {
-            Some(ref mut lhs) => lhs.min_mut(),
-            None => self,
+    fn nth(&self, k: usize) -> Option<&T> {
+        let mut node = self;
+        let mut k = k;
+        loop {
+            let lhs_size = get_size(&node.lhs);
+
+            if k < lhs_size {
+                node = node.lhs.as_ref()?;
+            } else if k == lhs_size {
+                return Some(&node.value);
+            } else if k < lhs_size + node.count() {
+                return node.duplicates.get(k - lhs_size - 1);
+            } else {
+                k -= lhs_size + node.count();
+                node = node.rhs.as_ref()?;
+            }
         }
     }
 
-    fn max_mut(&mut self) -> &mut Self {
-        match self.rhs {
-            Some(ref mut rhs) => rhs.max_mut(),
-            None => self,
+    fn rank(&self, value: &T) -> usize {
+        let mut rank = 0;
+        let mut node_opt = Some(self);
+        while let Some(node) = node_opt {
+            node_opt = match value.cmp(&node.value) {
+                Ordering::Less | Ordering::Equal => node.lhs.as_deref(),
+                Ordering::Greater => {
+                    rank += get_size(&node.lhs) + node.count();
+                    node.rhs.as_deref()
+                },
+            };
         }
+
+        rank
     }
 
     fn remove(node_opt: &mut NodeOpt<T>, value: &T) -> NodeOpt<T> {
-        let mut node = node_opt.take()?;
+        // The sizes on the path are only decreased if the value is contained.
+        let count = node_opt.as_ref()?.find(value)?.count();
 
-        match value.cmp(&node.value) {
-            Ordering::Equal => {
-                match (&mut node.lhs, &node.rhs) {
-                    (None, None) => {
-                        Some(node)
-                    },
-                    (Some(lhs), Some(_)) => {
-                        let max_node = lhs.max_mut();
-                        mem::swap(&mut node.value, &mut max_node.value);
+        let node_opt = Self::path_to(node_opt, value, |node| node.size -= count);
+        Self::remove_root(node_opt)
+    }
 
-                        let removed_node_opt = Self::remove(&mut node.lhs, value);
-                        *node_opt = Some(node);
-                        removed_node_opt
-                    },
-                    (None, Some(_)) => {
-                        let mut removed_node = node;
-                        *node_opt = removed_node.rhs.take();
-                        Some(removed_node)
-                    },
-                    (Some(_), None) => {
-                        let mut removed_node = node;
-                        *node_opt = removed_node.lhs.take();
-                        Some(removed_node)
-                    },
-                }
+    // Removes the first copy of the value, and the node if it has no other copies.
+    fn remove_one(node_opt: &mut NodeOpt<T>, value: &T) -> Option<T> {
+        node_opt.as_ref()?.find(value)?;
+
+        let node_opt = Self::path_to(node_opt, value, |node| node.size -= 1);
+        let node = node_opt.as_mut()?;
+        match node.duplicates.pop_front() {
+            Some(next) => {
+                node.size -= 1;
+                Some(mem::replace(&mut node.value, next))
             },
-            Ordering::Less => { 
-                let removed_node = Self::remove(&mut node.lhs, value);
+            None => Self::remove_root(node_opt).map(|node| node.value),
+        }
+    }
+
+    // Removes the `k`-th copy of the values in the subtree.
+    fn remove_nth(node_opt: &mut NodeOpt<T>, k: usize) -> Option<T> {
+        if k >= get_size(node_opt) {
+            return None;
+        }
+
+        let mut node_opt = node_opt;
+        let mut k = k;
+        loop {
+            let (lhs_size, count) = match node_opt.as_ref() {
+                Some(node) => (get_size(&node.lhs), node.count()),
+                None => return None,
+            };
+            if k == lhs_size && count == 1 {
+                return Self::remove_root(node_opt).map(|node| node.value);
+            }
+
+            let node = node_opt.as_mut()?;
+            if lhs_size <= k && k < lhs_size + count {
+                node.size -= 1;
+                return match k - lhs_size {
+                    0 => {
+                        let next = node.duplicates.pop_front()?;
+                        Some(mem::replace(&mut node.value, next))
+                    },
+                    i => node.duplicates.remove(i - 1),
+                };
+            }
+
+            node.size -= 1;
+            if k < lhs_size {
+                node_opt = &mut node.lhs;
+            } else {
+                k -= lhs_size + count;
+                node_opt = &mut node.rhs;
+            }
+        }
+    }
+
+    // Removes the root of the subtree.
+    fn remove_root(node_opt: &mut NodeOpt<T>) -> NodeOpt<T> {
+        let mut node = node_opt.take()?;
+
+        match (&node.lhs, &node.rhs) {
+            (Some(_), Some(_)) => {
+                // The node takes over the values of the greatest node of the lhs.
+                let mut max_node = Self::remove_max(&mut node.lhs)?;
+                mem::swap(&mut node.value, &mut max_node.value);
+                mem::swap(&mut node.duplicates, &mut max_node.duplicates);
+
+                node.update();
                 *node_opt = Some(node);
-                removed_node
+                Some(max_node)
             },
-            Ordering::Greater => {
-                let removed_node = Self::remove(&mut node.rhs, value);
-                *node_opt = Some(node);
-                removed_node
+            (None, _) => {
+                let mut removed_node = node;
+                *node_opt = removed_node.rhs.take();
+                Some(removed_node)
+            },
+            (Some(_), None) => {
+                let mut removed_node = node;
+                *node_opt = removed_node.lhs.take();
+                Some(removed_node)
             },
         }
     }
+
+    fn remove_max(node_opt: &mut NodeOpt<T>) -> NodeOpt<T> {
+        let mut max_node = node_opt.as_ref()?;
+        while let Some(rhs) = max_node.rhs.as_ref() {
+            max_node = rhs;
+        }
+        let count = max_node.count();
+
+        let mut node_opt = node_opt;
+        while node_opt.as_ref()?.rhs.is_some() {
+            let node = node_opt.as_mut()?;
+            node.size -= count;
+            node_opt = &mut node.rhs;
+        }
+
+        let mut removed_node = node_opt.take()?;
+        *node_opt = removed_node.lhs.take();
+        Some(removed_node)
+    }
 }
 
-impl<T: Ord + Debug> BinaryTree<T> where{
+impl<T: Ord> BinaryTree<T> {
     pub fn new() -> Self {
-        Self { root: None }
+        Self { root: None, multiset: false }
+    }
+
+    /// Constructs a new, empty BinaryTree<T> which keeps every copy of equal values.
+    pub fn new_multiset() -> Self {
+        Self { root: None, multiset: true }
+    }
+
+    /// Returns true if the tree keeps every copy of equal values.
+    pub fn is_multiset(&self) -> bool {
+        self.multiset
     }
 
+    /// Constructs a BinaryTree<T> from a slice of values in any order.
+    ///
+    /// To construct a tree from any iterator of values, use `collect` or `extend`.
     pub fn make_tree(array: &[T]) -> Self where
-        T: Copy {
+        T: Clone {
 
-        array
-        .iter()
-        .fold(Self::new(), |mut tree, v| {
-                tree.add(*v);
-                tree
-            }
-        )
+        array.iter().cloned().collect()
+    }
+
+    /// Constructs a perfectly balanced BinaryTree<T> from values in ascending order in O(n).
+    ///
+    /// Equal values are dropped except the first one, as by `add`. The values are
+    /// required to be in ascending order.
+    pub fn from_sorted_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values = sorted_dedup(iter);
+
+        let len = values.len();
+        Self { root: Node::from_sorted(&mut values.into_iter(), len), multiset: false }
     }
 
     pub fn find(&self, value: &T) -> Option<&T> {
         self.root.as_ref()?.find(value).map(|n| &n.value)
     }
 
-    pub fn add(&mut self, value: T) {
-        match self.root.as_mut() {
-            Some(root) => { root.add(value) },
-            None => { self.root = Some(Box::new(Node::new(value))) },
+    /// Returns the number of copies of a given value in the tree.
+    pub fn count(&self, value: &T) -> usize {
+        self.root
+            .as_ref()
+            .and_then(|root| root.find(value))
+            .map_or(0, |n| n.count())
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        get_size(&self.root)
+    }
+
+    /// Returns true if the tree contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the smallest value in the tree.
+    pub fn first(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(lhs) = node.lhs.as_ref() {
+            node = lhs;
         }
+
+        Some(&node.value)
+    }
+
+    /// Returns the greatest value in the tree.
+    pub fn last(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(rhs) = node.rhs.as_ref() {
+            node = rhs;
+        }
+
+        Some(node.duplicates.back().unwrap_or(&node.value))
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    /// Returns the `k`-th smallest value in the tree, counting from zero.
+    pub fn nth(&self, k: usize) -> Option<&T> {
+        self.root.as_ref()?.nth(k)
+    }
+
+    /// Returns the number of values in the tree less than a given value.
+    pub fn rank(&self, value: &T) -> usize {
+        self.root.as_ref().map_or(0, |root| root.rank(value))
+    }
+
+    /// Adds a given value.
+    ///
+    /// If an equal value is already contained, then the value is kept as another copy of
+    /// it if the tree is a multiset, and dropped otherwise.
+    pub fn add(&mut self, value: T) {
+        Node::add(&mut self.root, value, self.multiset);
     }
 
     pub fn remove(&mut self, value: &T) -> Option<T> {
         Node::remove(&mut self.root, value).map(|node| node.value)
     }
+
+    /// Removes one copy of a given value, which was added first of the copies.
+    pub fn remove_one(&mut self, value: &T) -> Option<T> {
+        Node::remove_one(&mut self.root, value)
+    }
+
+    /// Removes all copies of a given value, and returns them in order of addition.
+    pub fn remove_all(&mut self, value: &T) -> Vec<T> {
+        match Node::remove(&mut self.root, value) {
+            Some(mut node) => {
+                node.duplicates.push_front(node.value);
+                node.duplicates.into()
+            },
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns a cursor pointing at the smallest value not less than a given value.
+    ///
+    /// If there is no such value, then the cursor points at the ghost position.
+    pub fn lower_bound(&self, value: &T) -> Cursor<'_, T> {
+        Cursor::lower_bound(self, value)
+    }
+
+    /// Returns a cursor pointing at the smallest value greater than a given value.
+    ///
+    /// If there is no such value, then the cursor points at the ghost position.
+    pub fn upper_bound(&self, value: &T) -> Cursor<'_, T> {
+        Cursor::upper_bound(self, value)
+    }
+
+    /// Returns a mutable cursor pointing at the smallest value not less than a given value.
+    pub fn lower_bound_mut(&mut self, value: &T) -> CursorMut<'_, T> {
+        CursorMut::lower_bound(self, value)
+    }
+
+    /// Returns a mutable cursor pointing at the smallest value greater than a given value.
+    pub fn upper_bound_mut(&mut self, value: &T) -> CursorMut<'_, T> {
+        CursorMut::upper_bound(self, value)
+    }
 }
 
+// Drops the nodes one by one, since the recursive drop of a deep tree would overflow the
+// stack.
+impl<T: Ord> Drop for BinaryTree<T> {
+    fn drop(&mut self) {
+        let mut nodes: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = nodes.pop() {
+            nodes.extend(node.lhs.take());
+            nodes.extend(node.rhs.take());
+        }
+    }
+}
+
+impl<T: Ord> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> super::cursor::Tree for BinaryTree<T> {
+    type Node = Node<T>;
+
+    fn root(&self) -> Option<&Node<T>> {
+        self.root.as_deref()
+    }
+
+    fn len(&self) -> usize {
+        BinaryTree::len(self)
+    }
+
+    fn nth(&self, k: usize) -> Option<&T> {
+        BinaryTree::nth(self, k)
+    }
+
+    fn rank(&self, value: &T) -> usize {
+        BinaryTree::rank(self, value)
+    }
+
+    fn count(&self, value: &T) -> usize {
+        BinaryTree::count(self, value)
+    }
+
+    fn remove_nth(&mut self, k: usize) -> Option<T> {
+        Node::remove_nth(&mut self.root, k)
+    }
+}
+
+impl<T: Ord> super::iter::TreeNode for Node<T> {
+    type Value = T;
+
+    fn lhs(&self) -> Option<&Self> {
+        self.lhs.as_deref()
+    }
+
+    fn rhs(&self) -> Option<&Self> {
+        self.rhs.as_deref()
+    }
+
+    fn get(&self, i: usize) -> &T {
+        match i {
+            0 => &self.value,
+            _ => &self.duplicates[i - 1],
+        }
+    }
+
+    fn count(&self) -> usize {
+        Node::count(self)
+    }
+}
+
+impl<T: Ord> super::cursor::IndexedNode for Node<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinaryTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for BinaryTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<T: Ord> OrderedSet<T> for BinaryTree<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        Node::add(&mut self.root, value, self.multiset)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.find(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        BinaryTree::remove(self, value)
+    }
+
+    fn len(&self) -> usize {
+        BinaryTree::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        BinaryTree::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.first()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.last()
+    }
+}
+
+/// Creates a `BinaryTree` containing the given values.
+#[macro_export]
 macro_rules! binary_tree {
     ( $($x : expr),* ) => {
         {
-            let mut temp_bt = BinaryTree::new();
+            let mut temp_bt = $crate::tree::BinaryTree::new();
             $(
                 temp_bt.add($x);
             )*
@@ -153,6 +557,7 @@ macro_rules! binary_tree {
 mod tests {
     use std::cmp::Ordering;
     use super::BinaryTree;
+    use super::Node;
     use super::NodeOpt;
 
     //        7
@@ -275,4 +680,177 @@ mod tests {
         let nums = COMPLEX_TREE_SOURCE;
         test_remove(&nums[..]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_nth_and_rank() {
+        let mut binary_tree = BinaryTree::make_tree(&COMPLEX_TREE_SOURCE);
+        let mut sorted = COMPLEX_TREE_SOURCE.to_vec();
+        sorted.sort();
+
+        for (k, v) in sorted.iter().enumerate() {
+            assert_eq!(binary_tree.nth(k), Some(v));
+            assert_eq!(binary_tree.rank(v), k);
+        }
+        assert_eq!(binary_tree.len(), sorted.len());
+        assert_eq!(binary_tree.nth(sorted.len()), None);
+
+        for v in [7, 2, 11] {
+            binary_tree.remove(&v);
+            sorted.retain(|n| *n != v);
+            assert_eq!(binary_tree.len(), sorted.len());
+            for (k, v) in sorted.iter().enumerate() {
+                assert_eq!(binary_tree.nth(k), Some(v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiset() {
+        let mut binary_tree = BinaryTree::new_multiset();
+        for n in [7, 5, 7, 11, 5, 7] {
+            binary_tree.add(n);
+        }
+
+        assert!(binary_tree.is_multiset());
+        assert_eq!(binary_tree.count(&7), 3);
+        assert_eq!(binary_tree.count(&5), 2);
+        assert_eq!(binary_tree.count(&6), 0);
+
+        assert_eq!(binary_tree.remove_one(&7), Some(7));
+        assert_eq!(binary_tree.count(&7), 2);
+        assert!(is_valid_structure(&binary_tree.root));
+
+        assert_eq!(binary_tree.remove_all(&7), vec![7, 7]);
+        assert_eq!(binary_tree.count(&7), 0);
+        assert!(is_valid_structure(&binary_tree.root));
+        assert_eq!(binary_tree.count(&5), 2);
+        assert_eq!(binary_tree.count(&11), 1);
+
+        assert_eq!(binary_tree.remove_one(&11), Some(11));
+        assert_eq!(binary_tree.remove_one(&11), None);
+    }
+
+    #[test]
+    fn test_set_drops_duplicates() {
+        let mut binary_tree = BinaryTree::new();
+        binary_tree.add(7);
+        binary_tree.add(7);
+
+        assert_eq!(binary_tree.count(&7), 1);
+        assert_eq!(binary_tree.remove_all(&7), vec![7]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut binary_tree: BinaryTree<_> = COMPLEX_TREE_SOURCE.into_iter().collect();
+        let mut sorted = COMPLEX_TREE_SOURCE;
+        sorted.sort();
+
+        assert!(binary_tree.iter().eq(sorted.iter()));
+        assert_eq!(binary_tree.iter().len(), sorted.len());
+        assert_eq!(binary_tree.first(), Some(&2));
+        assert_eq!(binary_tree.last(), Some(&13));
+
+        binary_tree.remove(&7);
+        assert!(binary_tree.into_iter().eq(sorted.into_iter().filter(|&n| n != 7)));
+        assert_eq!(BinaryTree::<i32>::new().first(), None);
+    }
+
+    // Returns the height of the tree, which is 0 for an empty tree.
+    fn height<T: Ord>(node_opt: &NodeOpt<T>) -> usize {
+        node_opt.as_ref().map_or(0, |node| height(&node.lhs).max(height(&node.rhs)) + 1)
+    }
+
+    #[test]
+    fn test_from_sorted_iter() {
+        for n in 0..100 {
+            let binary_tree = BinaryTree::from_sorted_iter(0..n);
+
+            assert!(is_valid_structure(&binary_tree.root));
+            assert!(binary_tree.iter().copied().eq(0..n));
+            assert_eq!(binary_tree.len(), n);
+            // A perfectly balanced tree of n values has the minimum height.
+            let min_height = (n + 1).next_power_of_two().trailing_zeros() as usize;
+            assert_eq!(height(&binary_tree.root), min_height);
+        }
+
+        let binary_tree = BinaryTree::from_sorted_iter([1, 1, 2, 3, 3, 3]);
+        assert_eq!(binary_tree.len(), 3);
+        assert_eq!(binary_tree.nth(2), Some(&3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_unsorted_iter() {
+        BinaryTree::from_sorted_iter([1, 3, 2]);
+    }
+
+    #[test]
+    fn test_make_tree_without_copy() {
+        let words = ["pear", "apple", "fig"].map(String::from);
+        let mut binary_tree = BinaryTree::make_tree(&words);
+        binary_tree.extend(vec![String::from("kiwi")]);
+
+        assert!(binary_tree.iter().eq(["apple", "fig", "kiwi", "pear"].iter()));
+        assert_eq!(binary_tree.remove(&String::from("fig")), Some(String::from("fig")));
+    }
+
+    #[test]
+    fn test_key_without_copy_and_debug() {
+        use crate::tree::OrderedSet;
+
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        struct Key(String);
+
+        let key = |s: &str| Key(String::from(s));
+
+        let mut binary_tree: BinaryTree<Key> = ["pear", "apple"].into_iter().map(key).collect();
+        binary_tree.extend([key("fig")]);
+        assert!(OrderedSet::insert(&mut binary_tree, key("kiwi")));
+        assert!(OrderedSet::contains(&binary_tree, &key("fig")));
+        assert!(OrderedSet::min(&binary_tree) == Some(&key("apple")));
+        assert!(OrderedSet::remove(&mut binary_tree, &key("fig")) == Some(key("fig")));
+        assert!(binary_tree.lower_bound(&key("b")).peek() == Some(&key("kiwi")));
+
+        let sorted = BinaryTree::from_sorted_iter(binary_tree);
+        assert!(sorted.iter().eq([key("apple"), key("kiwi"), key("pear")].iter()));
+        assert!(BinaryTree::<Key>::default().is_empty());
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // A tree made by adding 0, 1, ..., n - 1 in order, whose height is its size.
+        let n = 1_000_000;
+        let mut binary_tree = BinaryTree::new_multiset();
+        for v in (0..n).rev() {
+            let mut node = Box::new(Node::new(v));
+            node.rhs = binary_tree.root.take();
+            node.update();
+            binary_tree.root = Some(node);
+        }
+
+        assert_eq!(binary_tree.find(&(n - 1)), Some(&(n - 1)));
+        assert_eq!(binary_tree.nth(n - 1), Some(&(n - 1)));
+        assert_eq!(binary_tree.rank(&(n - 1)), n - 1);
+
+        binary_tree.add(n - 1);
+        binary_tree.add(n);
+        assert_eq!(binary_tree.len(), n + 2);
+        assert_eq!(binary_tree.remove_one(&(n - 1)), Some(n - 1));
+        assert_eq!(binary_tree.remove(&n), Some(n));
+        assert_eq!(Node::remove_nth(&mut binary_tree.root, n - 2), Some(n - 2));
+        assert_eq!(binary_tree.len(), n - 1);
+        assert_eq!(binary_tree.last(), Some(&(n - 1)));
+        assert_eq!(binary_tree.iter().count(), n - 1);
+    }
+
+    #[test]
+    fn test_iter_multiset() {
+        let mut binary_tree = BinaryTree::new_multiset();
+        binary_tree.extend([7, 5, 7, 11, 5, 7]);
+
+        assert!(binary_tree.iter().eq([5, 5, 7, 7, 7, 11].iter()));
+        assert_eq!(binary_tree.last(), Some(&11));
+        assert!(binary_tree.into_iter().eq([5, 5, 7, 7, 7, 11]));
+    }
+}
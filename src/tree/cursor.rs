@@ -0,0 +1,348 @@
+use std::ptr;
+
+use super::iter::TreeNode;
+
+// A binary search tree whose values can be walked by cursors. The cursors of `BinaryTree`
+// and `AVL` are thin wrappers around `PathCursor` and `RankCursor`, which are written once
+// against this trait.
+pub(crate) trait Tree {
+    type Node: IndexedNode;
+
+    fn root(&self) -> Option<&Self::Node>;
+
+    fn len(&self) -> usize;
+
+    fn nth(&self, k: usize) -> Option<&Value<Self>>;
+
+    // Returns the number of values in the tree less than a given value.
+    fn rank(&self, value: &Value<Self>) -> usize;
+
+    // Returns the number of copies of a given value in the tree.
+    fn count(&self, value: &Value<Self>) -> usize;
+
+    fn remove_nth(&mut self, k: usize) -> Option<Value<Self>>;
+}
+
+// A node of a `Tree`, which knows the size of its subtree.
+pub(crate) trait IndexedNode: TreeNode {
+    // Returns the number of values of the subtree rooted at the node.
+    fn size(&self) -> usize;
+}
+
+pub(crate) type Value<S> = <<S as Tree>::Node as TreeNode>::Value;
+
+// A read-only cursor, which keeps the path from the root to the node pointed at, so that
+// each move takes O(1) amortized time.
+//
+// The path is empty at the ghost position, which lies after the greatest value and
+// before the smallest one.
+pub(crate) struct PathCursor<'a, N> {
+    root: Option<&'a N>,
+    // The root at the bottom, and the node pointed at on top.
+    path: Vec<&'a N>,
+    // The copy of the value in the node pointed at.
+    copy: usize,
+}
+
+// A cursor which can remove the value pointed at. It keeps only the rank of the value,
+// since the tree may be restructured by a removal, so that each move takes O(log n) time
+// in a balanced tree.
+pub(crate) struct RankCursor<'a, S: Tree> {
+    tree: &'a mut S,
+    // The rank of the value pointed at, which is the length of the tree at the ghost.
+    index: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl<'a, N: IndexedNode> PathCursor<'a, N> {
+    pub(crate) fn lower_bound<S: Tree<Node = N>>(tree: &'a S, value: &N::Value) -> Self {
+        Self::new(tree.root(), tree.rank(value))
+    }
+
+    pub(crate) fn upper_bound<S: Tree<Node = N>>(tree: &'a S, value: &N::Value) -> Self {
+        Self::new(tree.root(), tree.rank(value) + tree.count(value))
+    }
+
+    // Creates a cursor pointing at the `index`-th value, or at the ghost position if there
+    // is no such value.
+    fn new(root: Option<&'a N>, index: usize) -> Self {
+        let mut path = Vec::new();
+        let mut index = index;
+        let mut node_opt = root;
+        while let Some(node) = node_opt {
+            path.push(node);
+
+            let lhs_size = node.lhs().map_or(0, IndexedNode::size);
+            if index < lhs_size {
+                node_opt = node.lhs();
+            } else if index < lhs_size + node.count() {
+                return Self {
+                    root,
+                    path,
+                    copy: index - lhs_size,
+                };
+            } else {
+                index -= lhs_size + node.count();
+                node_opt = node.rhs();
+            }
+        }
+
+        Self {
+            root,
+            path: Vec::new(),
+            copy: 0,
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<&'a N::Value> {
+        self.path.last().map(|node| node.get(self.copy))
+    }
+
+    pub(crate) fn next(&mut self) -> Option<&'a N::Value> {
+        self.step(Side::Right);
+        self.peek()
+    }
+
+    pub(crate) fn prev(&mut self) -> Option<&'a N::Value> {
+        self.step(Side::Left);
+        self.peek()
+    }
+
+    // Moves to the adjacent value on a given side. Each node is entered and left once in a
+    // walk over all values.
+    fn step(&mut self, side: Side) {
+        let back = side.opposite();
+
+        match self.path.last().copied() {
+            // The ghost position is adjacent to the smallest and the greatest values.
+            None => {
+                if let Some(root) = self.root {
+                    self.path.push(root);
+                    self.descend(back);
+                }
+            }
+            Some(node) => {
+                let copy = match side {
+                    Side::Left => self.copy.checked_sub(1),
+                    Side::Right => Some(self.copy + 1).filter(|&copy| copy < node.count()),
+                };
+                if let Some(copy) = copy {
+                    self.copy = copy;
+                    return;
+                }
+
+                match child(node, side) {
+                    Some(next) => {
+                        self.path.push(next);
+                        self.descend(back);
+                    }
+                    // Goes up until coming from the `back` side, or up to the ghost.
+                    None => {
+                        while let Some(node) = self.path.pop() {
+                            let is_back_child = |parent| {
+                                child(parent, back).is_some_and(|back| ptr::eq(back, node))
+                            };
+                            if self.path.last().is_none_or(|&parent| is_back_child(parent)) {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.copy = match (self.path.last(), side) {
+            (Some(node), Side::Left) => node.count() - 1,
+            _ => 0,
+        };
+    }
+
+    // Goes down to the end of the subtree on a given side.
+    fn descend(&mut self, side: Side) {
+        while let Some(node) = self.path.last().and_then(|&node| child(node, side)) {
+            self.path.push(node);
+        }
+    }
+}
+
+impl<'a, S: Tree> RankCursor<'a, S> {
+    pub(crate) fn lower_bound(tree: &'a mut S, value: &Value<S>) -> Self {
+        let index = tree.rank(value);
+        Self { tree, index }
+    }
+
+    pub(crate) fn upper_bound(tree: &'a mut S, value: &Value<S>) -> Self {
+        let index = tree.rank(value) + tree.count(value);
+        Self { tree, index }
+    }
+
+    pub(crate) fn peek(&self) -> Option<&Value<S>> {
+        self.tree.nth(self.index)
+    }
+
+    pub(crate) fn next(&mut self) -> Option<&Value<S>> {
+        self.index = (self.index + 1) % (self.tree.len() + 1);
+        self.peek()
+    }
+
+    pub(crate) fn prev(&mut self) -> Option<&Value<S>> {
+        let len = self.tree.len();
+        self.index = (self.index + len) % (len + 1);
+        self.peek()
+    }
+
+    // The next value takes the rank of the removed one, so the cursor points at it.
+    pub(crate) fn remove_current(&mut self) -> Option<Value<S>> {
+        self.tree.remove_nth(self.index)
+    }
+
+    pub(crate) fn as_path_cursor(&self) -> PathCursor<'_, S::Node> {
+        PathCursor::new(self.tree.root(), self.index)
+    }
+}
+
+impl Side {
+    fn opposite(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+fn child<N: TreeNode>(node: &N, side: Side) -> Option<&N> {
+    match side {
+        Side::Left => node.lhs(),
+        Side::Right => node.rhs(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathCursor, RankCursor, Tree, TreeNode};
+    use crate::tree::{BinaryTree, AVL};
+
+    // The tests below are run on every tree. `new_multiset` constructs an empty multiset.
+    fn check_cursors<S>(new_multiset: fn() -> S)
+    where
+        S: Tree + FromIterator<i32> + Extend<i32>,
+        S::Node: TreeNode<Value = i32>,
+    {
+        lower_and_upper_bound(S::from_iter([10, 20, 30, 40]));
+        move_in_both_directions(S::from_iter([10, 20, 30]), S::from_iter([]));
+        remove_current(S::from_iter(0..10));
+
+        let mut multiset = new_multiset();
+        multiset.extend([1, 2, 2, 2, 3]);
+        multiset_copies(multiset);
+
+        walk_all_values(S::from_iter((0..1000).rev()));
+    }
+
+    fn lower_and_upper_bound<S: Tree<Node: TreeNode<Value = i32>>>(tree: S) {
+        assert_eq!(PathCursor::lower_bound(&tree, &20).peek(), Some(&20));
+        assert_eq!(PathCursor::upper_bound(&tree, &20).peek(), Some(&30));
+        assert_eq!(PathCursor::lower_bound(&tree, &25).peek(), Some(&30));
+        assert_eq!(PathCursor::upper_bound(&tree, &25).peek(), Some(&30));
+        assert_eq!(PathCursor::lower_bound(&tree, &0).peek(), Some(&10));
+        assert_eq!(PathCursor::lower_bound(&tree, &50).peek(), None);
+        assert_eq!(PathCursor::upper_bound(&tree, &40).peek(), None);
+    }
+
+    fn move_in_both_directions<S: Tree<Node: TreeNode<Value = i32>>>(mut tree: S, empty: S) {
+        let mut cursor = PathCursor::lower_bound(&tree, &20);
+        assert_eq!(cursor.next(), Some(&30));
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.next(), Some(&10));
+        assert_eq!(cursor.prev(), None);
+        assert_eq!(cursor.prev(), Some(&30));
+        assert_eq!(cursor.prev(), Some(&20));
+        assert_eq!(cursor.peek(), Some(&20));
+
+        let mut cursor = RankCursor::lower_bound(&mut tree, &20);
+        assert_eq!(cursor.next(), Some(&30));
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.next(), Some(&10));
+        assert_eq!(cursor.prev(), None);
+        assert_eq!(cursor.prev(), Some(&30));
+
+        let mut cursor = PathCursor::lower_bound(&empty, &0);
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.prev(), None);
+    }
+
+    fn remove_current<S: Tree<Node: TreeNode<Value = i32>>>(mut tree: S) {
+        let mut cursor = RankCursor::lower_bound(&mut tree, &3);
+
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.peek(), Some(&4));
+        assert_eq!(cursor.prev(), Some(&2));
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.as_path_cursor().peek(), Some(&4));
+        assert_eq!(cursor.as_path_cursor().prev(), Some(&1));
+
+        // Removes all even values.
+        let mut cursor = RankCursor::lower_bound(&mut tree, &0);
+        while let Some(v) = cursor.peek() {
+            if v % 2 == 0 {
+                cursor.remove_current();
+            } else {
+                cursor.next();
+            }
+        }
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(cursor.next(), Some(&1));
+        assert_eq!(cursor.next(), Some(&5));
+        assert_eq!(cursor.next(), Some(&7));
+        assert_eq!(cursor.next(), Some(&9));
+        assert_eq!(cursor.next(), None);
+    }
+
+    fn multiset_copies<S: Tree<Node: TreeNode<Value = i32>>>(mut tree: S) {
+        assert_eq!(PathCursor::upper_bound(&tree, &1).peek(), Some(&2));
+        assert_eq!(PathCursor::upper_bound(&tree, &2).peek(), Some(&3));
+
+        let mut cursor = PathCursor::lower_bound(&tree, &2);
+        assert_eq!(cursor.next(), Some(&2));
+        assert_eq!(cursor.next(), Some(&2));
+        assert_eq!(cursor.next(), Some(&3));
+        assert_eq!(cursor.prev(), Some(&2));
+
+        let mut cursor = RankCursor::lower_bound(&mut tree, &2);
+        assert_eq!(cursor.next(), Some(&2));
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.peek(), Some(&2));
+        assert_eq!(cursor.prev(), Some(&2));
+        assert_eq!(cursor.prev(), Some(&1));
+        assert_eq!(tree.count(&2), 2);
+    }
+
+    fn walk_all_values<S: Tree<Node: TreeNode<Value = i32>>>(tree: S) {
+        let mut cursor = PathCursor::lower_bound(&tree, &0);
+        for v in 0..1000 {
+            assert_eq!(cursor.peek(), Some(&v));
+            cursor.next();
+        }
+        assert_eq!(cursor.peek(), None);
+
+        for v in (0..1000).rev() {
+            assert_eq!(cursor.prev(), Some(&v));
+        }
+        assert_eq!(cursor.prev(), None);
+    }
+
+    #[test]
+    fn binary_tree_cursors() {
+        check_cursors(BinaryTree::new_multiset);
+    }
+
+    #[test]
+    fn avl_cursors() {
+        check_cursors(AVL::new_multiset);
+    }
+}
@@ -0,0 +1,232 @@
+use std::cmp::Ordering;
+use std::iter::FusedIterator;
+
+// A node of a binary search tree, which holds one value and its copies. The iterators of
+// the trees are thin wrappers around `InOrder` and `into_sorted_vec`, which are written
+// once against this trait and `OwnedNode`.
+pub(crate) trait TreeNode {
+    type Value;
+
+    fn lhs(&self) -> Option<&Self>;
+
+    fn rhs(&self) -> Option<&Self>;
+
+    // Returns the `i`-th copy of the value in the node, in order of addition.
+    fn get(&self, i: usize) -> &Self::Value;
+
+    // Returns the number of copies of the value in the node.
+    fn count(&self) -> usize;
+}
+
+// A node owned by its parent, which can be taken apart to move its values out.
+pub(crate) trait OwnedNode: Sized {
+    type Value;
+
+    fn take_lhs(&mut self) -> Option<Self>;
+
+    fn take_rhs(&mut self) -> Option<Self>;
+
+    // Moves the copies of the value in the node to the end of `values`, in order of
+    // addition.
+    fn move_values(self, values: &mut Vec<Self::Value>);
+}
+
+// An iterator over the values of a tree in ascending order, which can also be walked from
+// the greatest value.
+pub(crate) struct InOrder<'a, N> {
+    // The top of `front` is the node of the smallest value not yet yielded, and the top of
+    // `back` is that of the greatest one. Below them are the ancestors whose values and
+    // subtrees on the other side are not yet yielded.
+    front: Vec<&'a N>,
+    back: Vec<&'a N>,
+    // The numbers of copies already yielded from the tops of `front` and `back`.
+    front_yielded: usize,
+    back_yielded: usize,
+    // The number of values not yet yielded, which tells when the two ends meet.
+    len: usize,
+}
+
+impl<'a, N: TreeNode> InOrder<'a, N> {
+    pub(crate) fn new(root: Option<&'a N>, len: usize) -> Self {
+        let mut iter = Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            front_yielded: 0,
+            back_yielded: 0,
+            len,
+        };
+        push_path(&mut iter.front, root, N::lhs);
+        push_path(&mut iter.back, root, N::rhs);
+        iter
+    }
+}
+
+impl<'a, N: TreeNode> Iterator for InOrder<'a, N> {
+    type Item = &'a N::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let node = *self.front.last()?;
+        let value = node.get(self.front_yielded);
+        self.front_yielded += 1;
+
+        if self.front_yielded == node.count() {
+            self.front.pop();
+            self.front_yielded = 0;
+            push_path(&mut self.front, node.rhs(), N::lhs);
+        }
+
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<N: TreeNode> DoubleEndedIterator for InOrder<'_, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let node = *self.back.last()?;
+        self.back_yielded += 1;
+        let value = node.get(node.count() - self.back_yielded);
+
+        if self.back_yielded == node.count() {
+            self.back.pop();
+            self.back_yielded = 0;
+            push_path(&mut self.back, node.lhs(), N::rhs);
+        }
+
+        self.len -= 1;
+        Some(value)
+    }
+}
+
+impl<N: TreeNode> ExactSizeIterator for InOrder<'_, N> {}
+
+impl<N: TreeNode> FusedIterator for InOrder<'_, N> {}
+
+// Pushes the nodes from `node_opt` down to the end of its subtree, following `child`.
+fn push_path<'a, N>(
+    stack: &mut Vec<&'a N>,
+    mut node_opt: Option<&'a N>,
+    child: impl Fn(&'a N) -> Option<&'a N>,
+) {
+    while let Some(node) = node_opt {
+        stack.push(node);
+        node_opt = child(node);
+    }
+}
+
+// Moves the values out of a tree of `len` values in ascending order, without recursion.
+pub(crate) fn into_sorted_vec<N: OwnedNode>(root: Option<N>, len: usize) -> Vec<N::Value> {
+    let mut values = Vec::with_capacity(len);
+    let mut stack = Vec::new();
+    let mut node_opt = root;
+
+    loop {
+        while let Some(mut node) = node_opt {
+            node_opt = node.take_lhs();
+            stack.push(node);
+        }
+
+        let Some(mut node) = stack.pop() else {
+            break;
+        };
+        node_opt = node.take_rhs();
+        node.move_values(&mut values);
+    }
+
+    values
+}
+
+// Collects values in ascending order, dropping equal values except the first one.
+pub(crate) fn sorted_dedup<T: Ord>(iter: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut values: Vec<T> = Vec::new();
+    for value in iter {
+        if let Some(last) = values.last() {
+            match value.cmp(last) {
+                Ordering::Less => panic!("The values are required to be in ascending order."),
+                Ordering::Equal => continue,
+                Ordering::Greater => (),
+            }
+        }
+        values.push(value);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sorted_dedup;
+    use crate::tree::{BinaryTree, OrderedSet, RedBlackTree, AVL};
+
+    // Walks a set of 0..100 from both ends until they meet.
+    fn check_both_ends<S: OrderedSet<i32>>() {
+        let set: S = (0..100).rev().collect();
+        assert!(set.iter().rev().copied().eq((0..100).rev()));
+
+        let mut iter = set.iter();
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        for i in 0..100 {
+            assert_eq!(iter.len(), 100 - i);
+            match i % 3 {
+                0 => back.push(*iter.next_back().unwrap()),
+                _ => front.push(*iter.next().unwrap()),
+            }
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        back.reverse();
+        front.extend(back);
+        assert_eq!(front, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn both_ends() {
+        check_both_ends::<BinaryTree<i32>>();
+        check_both_ends::<AVL<i32>>();
+        check_both_ends::<RedBlackTree<i32>>();
+    }
+
+    #[test]
+    fn multiset_copies() {
+        let mut avl = AVL::new_multiset();
+        let mut binary_tree = BinaryTree::new_multiset();
+        avl.extend([2, 1, 2, 3, 2]);
+        binary_tree.extend([2, 1, 2, 3, 2]);
+
+        assert!(avl.iter().rev().eq([3, 2, 2, 2, 1].iter()));
+        assert!(binary_tree.iter().rev().eq([3, 2, 2, 2, 1].iter()));
+
+        // The two ends meet within the copies of 2.
+        let mut iter = avl.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn sorted_dedup_drops_copies() {
+        assert_eq!(sorted_dedup([1, 1, 2, 3, 3, 3]), vec![1, 2, 3]);
+        assert_eq!(sorted_dedup(Vec::<i32>::new()), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ascending order")]
+    fn sorted_dedup_unsorted() {
+        sorted_dedup([1, 3, 2]);
+    }
+}
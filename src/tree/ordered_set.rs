@@ -0,0 +1,46 @@
+/// An ordered set of values, implemented by the trees of this module.
+///
+/// Code written against this trait works with any of the trees, which can also be built
+/// from and turned into iterators of values.
+pub trait OrderedSet<T: Ord>:
+    Default + FromIterator<T> + Extend<T> + IntoIterator<Item = T>
+{
+    /// An iterator over the values in ascending order, which can also be walked from the
+    /// greatest value.
+    type Iter<'a>: DoubleEndedIterator<Item = &'a T> + ExactSizeIterator
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Inserts a value.
+    ///
+    /// If the value has been added then true is returned. If an equal value is already
+    /// contained and the set keeps only one copy of it, then false is returned.
+    fn insert(&mut self, value: T) -> bool;
+
+    /// Returns true if the set contains a given value.
+    fn contains(&self, value: &T) -> bool;
+
+    /// Removes a given value.
+    ///
+    /// If the value is found then Option::Some is returned, containing the matching
+    /// value. If the value is not found then Option::None is returned.
+    fn remove(&mut self, value: &T) -> Option<T>;
+
+    /// Returns the number of values in the set.
+    fn len(&self) -> usize;
+
+    /// Returns true if the set contains no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// Returns the smallest value in the set.
+    fn min(&self) -> Option<&T>;
+
+    /// Returns the greatest value in the set.
+    fn max(&self) -> Option<&T>;
+}
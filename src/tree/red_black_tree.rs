@@ -0,0 +1,644 @@
+use std::cmp::Ordering;
+use std::mem;
+
+use super::OrderedSet;
+
+mod iter;
+
+pub use iter::{IntoIter, Iter};
+
+/// A red-black tree.
+///
+/// The tree keeps the following properties, so that its height is at most 2 log2(n + 1).
+/// - The root is black.
+/// - A red node has no red child.
+/// - Every path from a node down to its leaves contains the same number of black nodes.
+pub struct RedBlackTree<T: Ord> {
+    root: NodeOpt<T>,
+    len: usize,
+    // The number of rotations performed on the tree.
+    rotations: usize,
+}
+
+struct Node<T: Ord> {
+    value: T,
+    color: Color,
+    lhs: NodeOpt<T>,
+    rhs: NodeOpt<T>,
+}
+
+type NodeOpt<T> = Option<Box<Node<T>>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+impl<T: Ord> RedBlackTree<T> {
+    /// Constructs a new, empty RedBlackTree<T>.
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            rotations: 0,
+        }
+    }
+
+    /// Finds a node for a given value.
+    ///
+    /// If the value is found then Option::Some is returned, containing the matching
+    /// value. If the value is not found then Option::None is returned.
+    pub fn find(&self, value: &T) -> Option<&T> {
+        let mut node_opt = &self.root;
+        while let Some(node) = node_opt {
+            node_opt = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Less => &node.lhs,
+                Ordering::Greater => &node.rhs,
+            };
+        }
+
+        None
+    }
+
+    /// Adds a node containing a given value.
+    ///
+    /// If an equal value is already contained, then the value is dropped.
+    pub fn add(&mut self, value: T) {
+        if Node::add(&mut self.root, value, &mut self.rotations) {
+            self.len += 1;
+        }
+
+        if let Some(root) = self.root.as_mut() {
+            root.color = Color::Black;
+        }
+    }
+
+    /// Removes a node containing a given value.
+    ///
+    /// If the value is found then the node containing it is removed and Option::Some
+    /// is returned, containing the matching value. If the value is not found then
+    /// Option::None is returned.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let (removed, _) = Node::remove(&mut self.root, value, &mut self.rotations);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+
+        if let Some(root) = self.root.as_mut() {
+            root.color = Color::Black;
+        }
+        removed
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the smallest value in the tree.
+    pub fn first(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(lhs) = node.lhs.as_ref() {
+            node = lhs;
+        }
+
+        Some(&node.value)
+    }
+
+    /// Returns the greatest value in the tree.
+    pub fn last(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(rhs) = node.rhs.as_ref() {
+            node = rhs;
+        }
+
+        Some(&node.value)
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    /// Returns the number of rotations performed to keep the tree balanced.
+    ///
+    /// A double rotation is counted as two.
+    pub fn rotation_count(&self) -> usize {
+        self.rotations
+    }
+}
+
+impl<T: Ord> Default for RedBlackTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for RedBlackTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for RedBlackTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<T: Ord> OrderedSet<T> for RedBlackTree<T> {
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        let len = self.len;
+        self.add(value);
+        self.len > len
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.find(value).is_some()
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        RedBlackTree::remove(self, value)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        RedBlackTree::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.first()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.last()
+    }
+}
+
+// Each node holds a single copy of its value, since equal values are dropped.
+impl<T: Ord> super::iter::TreeNode for Node<T> {
+    type Value = T;
+
+    fn lhs(&self) -> Option<&Self> {
+        self.lhs.as_deref()
+    }
+
+    fn rhs(&self) -> Option<&Self> {
+        self.rhs.as_deref()
+    }
+
+    fn get(&self, _: usize) -> &T {
+        &self.value
+    }
+
+    fn count(&self) -> usize {
+        1
+    }
+}
+
+impl<T: Ord> Node<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            color: Color::Red,
+            lhs: None,
+            rhs: None,
+        }
+    }
+
+    // Returns true if the value has been added.
+    // The added node is red, and a red node with a red child is fixed by its parent.
+    fn add(node_opt: &mut NodeOpt<T>, value: T, rotations: &mut usize) -> bool {
+        let Some(node) = node_opt.as_mut() else {
+            *node_opt = Some(Box::new(Node::new(value)));
+            return true;
+        };
+
+        let added = match value.cmp(&node.value) {
+            Ordering::Less => Self::add(&mut node.lhs, value, rotations),
+            Ordering::Greater => Self::add(&mut node.rhs, value, rotations),
+            Ordering::Equal => false,
+        };
+
+        if added {
+            Self::fix_red_red(node_opt, rotations);
+        }
+        added
+    }
+
+    // Fixes a red child of the node which has a red child.
+    fn fix_red_red(node_opt: &mut NodeOpt<T>, rotations: &mut usize) {
+        let node = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+
+        let lhs_violated = is_red(&node.lhs)
+            && node
+                .lhs
+                .as_ref()
+                .is_some_and(|lhs| is_red(&lhs.lhs) || is_red(&lhs.rhs));
+        let rhs_violated = is_red(&node.rhs)
+            && node
+                .rhs
+                .as_ref()
+                .is_some_and(|rhs| is_red(&rhs.lhs) || is_red(&rhs.rhs));
+
+        if !lhs_violated && !rhs_violated {
+            return;
+        }
+
+        if is_red(&node.lhs) && is_red(&node.rhs) {
+            // Both children are red, so that the red is pushed up to the node.
+            node.color = Color::Red;
+            set_color(&mut node.lhs, Color::Black);
+            set_color(&mut node.rhs, Color::Black);
+            return;
+        }
+
+        if lhs_violated {
+            if node.lhs.as_ref().is_some_and(|lhs| is_red(&lhs.rhs)) {
+                rotate_left(&mut node.lhs, rotations);
+            }
+            rotate_right(node_opt, rotations);
+        } else {
+            if node.rhs.as_ref().is_some_and(|rhs| is_red(&rhs.lhs)) {
+                rotate_right(&mut node.rhs, rotations);
+            }
+            rotate_left(node_opt, rotations);
+        }
+
+        let node = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+        node.color = Color::Black;
+        set_color(&mut node.lhs, Color::Red);
+        set_color(&mut node.rhs, Color::Red);
+    }
+
+    // Returns the removed value, and true if the black height of the tree has decreased.
+    fn remove(node_opt: &mut NodeOpt<T>, value: &T, rotations: &mut usize) -> (Option<T>, bool) {
+        let Some(node) = node_opt.as_mut() else {
+            return (None, false);
+        };
+
+        match value.cmp(&node.value) {
+            Ordering::Less => {
+                let (removed, shortened) = Self::remove(&mut node.lhs, value, rotations);
+                let shortened = shortened && Self::fix_short_lhs(node_opt, rotations);
+                (removed, shortened)
+            }
+            Ordering::Greater => {
+                let (removed, shortened) = Self::remove(&mut node.rhs, value, rotations);
+                let shortened = shortened && Self::fix_short_rhs(node_opt, rotations);
+                (removed, shortened)
+            }
+            Ordering::Equal if node.lhs.is_some() && node.rhs.is_some() => {
+                // The node takes over the smallest value of the rhs.
+                let (min, shortened) = Self::remove_min(&mut node.rhs, rotations);
+                let removed = mem::replace(&mut node.value, min);
+                let shortened = shortened && Self::fix_short_rhs(node_opt, rotations);
+                (Some(removed), shortened)
+            }
+            Ordering::Equal => {
+                let (removed, shortened) = Self::remove_root(node_opt);
+                (Some(removed), shortened)
+            }
+        }
+    }
+
+    // Returns the smallest value, and true if the black height of the tree has decreased.
+    fn remove_min(node_opt: &mut NodeOpt<T>, rotations: &mut usize) -> (T, bool) {
+        let node = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+
+        if node.lhs.is_some() {
+            let (min, shortened) = Self::remove_min(&mut node.lhs, rotations);
+            let shortened = shortened && Self::fix_short_lhs(node_opt, rotations);
+            (min, shortened)
+        } else {
+            Self::remove_root(node_opt)
+        }
+    }
+
+    // Removes the root of the tree, which is required to have at most one child.
+    fn remove_root(node_opt: &mut NodeOpt<T>) -> (T, bool) {
+        let mut node = node_opt
+            .take()
+            .expect("The node is required not to be Option::None.");
+        let mut child = node.lhs.take().or_else(|| node.rhs.take());
+
+        // The only child of a node is red, and a red node has no child.
+        let shortened = match (node.color, child.as_mut()) {
+            (Color::Red, _) => false,
+            (Color::Black, Some(child)) => {
+                child.color = Color::Black;
+                false
+            }
+            (Color::Black, None) => true,
+        };
+
+        *node_opt = child;
+        (node.value, shortened)
+    }
+
+    // Fixes the tree whose lhs has lost one black node in its paths.
+    // Returns true if the black height of the tree has decreased.
+    fn fix_short_lhs(node_opt: &mut NodeOpt<T>, rotations: &mut usize) -> bool {
+        let node = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+
+        if is_red(&node.rhs) {
+            // Makes the sibling black, by rotating the red sibling above the node.
+            node.color = Color::Red;
+            set_color(&mut node.rhs, Color::Black);
+            rotate_left(node_opt, rotations);
+
+            let top = node_opt
+                .as_mut()
+                .expect("The node is required not to be Option::None.");
+            Self::fix_short_lhs(&mut top.lhs, rotations);
+            return false;
+        }
+
+        let sibling = node
+            .rhs
+            .as_mut()
+            .expect("The sibling is required not to be Option::None, because it has black nodes.");
+
+        if !is_red(&sibling.lhs) && !is_red(&sibling.rhs) {
+            sibling.color = Color::Red;
+            return if node.color == Color::Red {
+                node.color = Color::Black;
+                false
+            } else {
+                true
+            };
+        }
+
+        if !is_red(&sibling.rhs) {
+            sibling.color = Color::Red;
+            set_color(&mut sibling.lhs, Color::Black);
+            rotate_right(&mut node.rhs, rotations);
+        }
+
+        let color = node.color;
+        rotate_left(node_opt, rotations);
+
+        let top = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+        top.color = color;
+        set_color(&mut top.lhs, Color::Black);
+        set_color(&mut top.rhs, Color::Black);
+        false
+    }
+
+    // Fixes the tree whose rhs has lost one black node in its paths.
+    // Returns true if the black height of the tree has decreased.
+    fn fix_short_rhs(node_opt: &mut NodeOpt<T>, rotations: &mut usize) -> bool {
+        let node = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+
+        if is_red(&node.lhs) {
+            node.color = Color::Red;
+            set_color(&mut node.lhs, Color::Black);
+            rotate_right(node_opt, rotations);
+
+            let top = node_opt
+                .as_mut()
+                .expect("The node is required not to be Option::None.");
+            Self::fix_short_rhs(&mut top.rhs, rotations);
+            return false;
+        }
+
+        let sibling = node
+            .lhs
+            .as_mut()
+            .expect("The sibling is required not to be Option::None, because it has black nodes.");
+
+        if !is_red(&sibling.lhs) && !is_red(&sibling.rhs) {
+            sibling.color = Color::Red;
+            return if node.color == Color::Red {
+                node.color = Color::Black;
+                false
+            } else {
+                true
+            };
+        }
+
+        if !is_red(&sibling.lhs) {
+            sibling.color = Color::Red;
+            set_color(&mut sibling.rhs, Color::Black);
+            rotate_left(&mut node.lhs, rotations);
+        }
+
+        let color = node.color;
+        rotate_right(node_opt, rotations);
+
+        let top = node_opt
+            .as_mut()
+            .expect("The node is required not to be Option::None.");
+        top.color = color;
+        set_color(&mut top.lhs, Color::Black);
+        set_color(&mut top.rhs, Color::Black);
+        false
+    }
+}
+
+// Returns true if the node is red. Option::None is regarded as black.
+fn is_red<T: Ord>(node_opt: &NodeOpt<T>) -> bool {
+    node_opt
+        .as_ref()
+        .is_some_and(|node| node.color == Color::Red)
+}
+
+fn set_color<T: Ord>(node_opt: &mut NodeOpt<T>, color: Color) {
+    if let Some(node) = node_opt.as_mut() {
+        node.color = color;
+    }
+}
+
+// The node and the right child are required to be Option::Some.
+fn rotate_left<T: Ord>(node_opt: &mut NodeOpt<T>, rotations: &mut usize) {
+    let mut node = node_opt
+        .take()
+        .expect("The given node is required not to be None.");
+    let mut rhs = node
+        .rhs
+        .take()
+        .expect("The rhs of the given node is required not to be None.");
+
+    node.rhs = rhs.lhs.take();
+    rhs.lhs = Some(node);
+    *node_opt = Some(rhs);
+    *rotations += 1;
+}
+
+// The node and the left child are required to be Option::Some.
+fn rotate_right<T: Ord>(node_opt: &mut NodeOpt<T>, rotations: &mut usize) {
+    let mut node = node_opt
+        .take()
+        .expect("The given node is required not to be None.");
+    let mut lhs = node
+        .lhs
+        .take()
+        .expect("The lhs of the given node is required not to be None.");
+
+    node.lhs = lhs.rhs.take();
+    lhs.rhs = Some(node);
+    *node_opt = Some(lhs);
+    *rotations += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    const COMPLEX_TREE_SOURCE: [i32; 9] = [7, 5, 4, 2, 6, 11, 9, 10, 13];
+
+    // Returns the number of black nodes in every path to the leaves, if the number is the
+    // same for all paths and no red node has a red child.
+    fn black_height<T: Ord>(node_opt: &NodeOpt<T>) -> Option<usize> {
+        let Some(node) = node_opt else {
+            return Some(0);
+        };
+        if node.color == Color::Red && (is_red(&node.lhs) || is_red(&node.rhs)) {
+            return None;
+        }
+
+        let lhs_height = black_height(&node.lhs)?;
+        let rhs_height = black_height(&node.rhs)?;
+        if lhs_height != rhs_height {
+            return None;
+        }
+
+        Some(lhs_height + usize::from(node.color == Color::Black))
+    }
+
+    fn is_valid_structure<T: Ord>(node_opt: &NodeOpt<T>) -> bool {
+        let Some(node) = node_opt else {
+            return true;
+        };
+
+        node.lhs.as_ref().is_none_or(|lhs| lhs.value < node.value)
+            && node.rhs.as_ref().is_none_or(|rhs| rhs.value > node.value)
+            && is_valid_structure(&node.lhs)
+            && is_valid_structure(&node.rhs)
+    }
+
+    fn is_valid<T: Ord>(tree: &RedBlackTree<T>) -> bool {
+        !is_red(&tree.root) && black_height(&tree.root).is_some() && is_valid_structure(&tree.root)
+    }
+
+    fn height<T: Ord>(node_opt: &NodeOpt<T>) -> usize {
+        node_opt
+            .as_ref()
+            .map_or(0, |node| height(&node.lhs).max(height(&node.rhs)) + 1)
+    }
+
+    #[test]
+    fn find_and_remove() {
+        let mut tree = RedBlackTree::new();
+        for v in COMPLEX_TREE_SOURCE {
+            tree.add(v);
+            assert!(is_valid(&tree));
+        }
+        assert_eq!(tree.len(), COMPLEX_TREE_SOURCE.len());
+
+        for v in COMPLEX_TREE_SOURCE {
+            assert_eq!(tree.find(&v), Some(&v));
+        }
+        assert_eq!(tree.find(&3), None);
+
+        for v in COMPLEX_TREE_SOURCE {
+            assert_eq!(tree.remove(&v), Some(v));
+            assert_eq!(tree.remove(&v), None);
+            assert_eq!(tree.find(&v), None);
+            assert!(is_valid(&tree));
+        }
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn add_duplicates() {
+        let mut tree = RedBlackTree::new();
+        tree.add(1);
+        tree.add(1);
+
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn height_of_sequential_inserts() {
+        let n = 1 << 16;
+        let mut tree = RedBlackTree::new();
+        for v in 0..n {
+            tree.add(v);
+        }
+
+        assert!(is_valid(&tree));
+        assert!(height(&tree.root) <= 2 * 17);
+        // Every insertion after the first two takes at most two rotations.
+        assert!(tree.rotation_count() < 2 * n);
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut state: u64 = 1;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            state >> 33
+        };
+
+        for max in [10, 1_000, 100_000] {
+            let mut tree = RedBlackTree::new();
+            let mut expected = BTreeSet::new();
+
+            for i in 0..100_000 {
+                let value = next() % max;
+                if next() % 5 < 3 {
+                    tree.add(value);
+                    expected.insert(value);
+                } else {
+                    assert_eq!(tree.remove(&value), expected.take(&value));
+                }
+                assert_eq!(tree.find(&value), expected.get(&value));
+                assert_eq!(tree.len(), expected.len());
+
+                if i % 10_000 == 0 {
+                    assert!(is_valid(&tree));
+                    assert!(tree.iter().eq(expected.iter()));
+                }
+            }
+            assert!(is_valid(&tree));
+            assert_eq!(tree.first(), expected.first());
+            assert_eq!(tree.last(), expected.last());
+            assert!(tree.into_iter().eq(expected));
+        }
+    }
+}
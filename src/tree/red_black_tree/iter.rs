@@ -0,0 +1,114 @@
+use std::iter::FusedIterator;
+use std::vec;
+
+use super::{Node, RedBlackTree};
+use crate::tree::iter::{into_sorted_vec, InOrder, OwnedNode};
+
+/// An iterator over the values in a `RedBlackTree`, in ascending order.
+///
+/// This struct is created by `RedBlackTree::iter`.
+pub struct Iter<'a, T: Ord> {
+    inner: InOrder<'a, Node<T>>,
+}
+
+/// An owning iterator over the values in a `RedBlackTree`, in ascending order.
+///
+/// This struct is created by `RedBlackTree::into_iter`.
+pub struct IntoIter<T> {
+    values: vec::IntoIter<T>,
+}
+
+impl<'a, T: Ord> Iter<'a, T> {
+    pub(super) fn new(tree: &'a RedBlackTree<T>) -> Self {
+        Self {
+            inner: InOrder::new(tree.root.as_deref(), tree.len()),
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: Ord> FusedIterator for Iter<'_, T> {}
+
+impl<T: Ord> IntoIter<T> {
+    pub(super) fn new(mut tree: RedBlackTree<T>) -> Self {
+        let len = tree.len();
+        Self {
+            values: into_sorted_vec(tree.root.take(), len).into_iter(),
+        }
+    }
+}
+
+impl<T: Ord> OwnedNode for Box<Node<T>> {
+    type Value = T;
+
+    fn take_lhs(&mut self) -> Option<Self> {
+        self.lhs.take()
+    }
+
+    fn take_rhs(&mut self) -> Option<Self> {
+        self.rhs.take()
+    }
+
+    fn move_values(self, values: &mut Vec<T>) {
+        values.push(self.value);
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.values.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<'a, T: Ord> IntoIterator for &'a RedBlackTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> IntoIterator for RedBlackTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
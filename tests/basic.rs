@@ -0,0 +1,33 @@
+use algorithms_by_rust::basic::{binary_search, Queue, Stack};
+
+#[test]
+fn stack_is_last_in_first_out() {
+    let mut stack = Stack::new();
+    stack.push(1);
+    stack.push(2);
+
+    assert_eq!(stack.peek(), Some(&2));
+    assert_eq!(stack.pop(), Some(2));
+    assert_eq!(stack.pop(), Some(1));
+    assert_eq!(stack.pop(), None);
+}
+
+#[test]
+fn queue_is_first_in_first_out() {
+    let mut que = Queue::default();
+    que.push(1);
+    que.push(2);
+
+    assert_eq!(que.peek(), Some(&1));
+    assert_eq!(que.pop(), Some(1));
+    assert_eq!(que.pop(), Some(2));
+    assert_eq!(que.pop(), None);
+}
+
+#[test]
+fn binary_search_sorted_slice() {
+    let array = [-3, 1, 4, 8, 15];
+
+    assert_eq!(binary_search(&array, &4), Some(2));
+    assert_eq!(binary_search(&array, &5), None);
+}
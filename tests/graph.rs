@@ -0,0 +1,104 @@
+use algorithms_by_rust::graph::{
+    bellman_ford, bfs, dfs, dijkstra, floyd_warshall, johnson, kruskal, prim,
+    strongly_connected_components, topological_sort, zero_one_bfs, Cycle, Distance, Edge, EdgeKind,
+    Graph, TwoSat,
+};
+
+#[test]
+fn build_graph() {
+    let mut g = Graph::new_undirected(3);
+    g.add_edge(0, 1, 2u32);
+    g.add_edge(1, 2, 3u32);
+
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(
+        g.edges_from(1).copied().collect::<Vec<_>>(),
+        vec![Edge::new(1, 0, 2), Edge::new(1, 2, 3)]
+    );
+}
+
+#[test]
+fn dijkstra_distances() {
+    let g = Graph::from_directed_edges(4, vec![(0, 1, 4), (0, 2, 1), (2, 1, 2)]);
+
+    let tree = dijkstra(&g, 0).unwrap();
+    assert_eq!(tree.distance(1), Some(3));
+    assert_eq!(tree.distance(3), None);
+    assert_eq!(tree.path_to(1), Some(vec![0, 2, 1]));
+    assert_eq!(tree.path_to(3), None);
+}
+
+#[test]
+fn bellman_ford_negative_cycle() {
+    let g = Graph::from_directed_edges(4, vec![(0, 1, 1), (1, 2, -2), (2, 1, 1), (0, 3, -1)]);
+
+    let result = bellman_ford(&g, 0).unwrap();
+    assert_eq!(result.distance(3), Distance::Finite(-1));
+    assert_eq!(result.distance(2), Distance::NegativeInfinity);
+    assert_eq!(result.negative_cycle().map(|c| c.len()), Some(2));
+}
+
+#[test]
+fn all_pairs_shortest_paths() {
+    let g = Graph::from_directed_edges(3, vec![(0, 1, 2), (1, 2, -1), (0, 2, 3)]);
+
+    let expected = floyd_warshall(&g).unwrap();
+    let matrix = johnson(&g).unwrap();
+    assert_eq!(matrix, expected);
+    assert_eq!(matrix.distance(0, 2), Distance::Finite(1));
+    assert_eq!(matrix.path(0, 2), Some(vec![0, 1, 2]));
+    assert_eq!(matrix.distance(2, 0), Distance::Unreachable);
+}
+
+#[test]
+fn breadth_first_search() {
+    let g = Graph::from_undirected_edges(4, vec![(0, 1, 0u8), (1, 2, 1), (0, 2, 1), (2, 3, 0)]);
+
+    let tree = bfs(&g, 0);
+    assert_eq!(tree.distance(3), Some(2));
+
+    let tree = zero_one_bfs(&g, 0).unwrap();
+    assert_eq!(tree.distance(3), Some(1));
+    assert_eq!(tree.path_to(1), Some(vec![0, 1]));
+}
+
+#[test]
+fn depth_first_search() {
+    let dag = Graph::from_directed_edges(3, vec![(2, 0, ()), (0, 1, ()), (2, 1, ())]);
+    assert_eq!(topological_sort(&dag), Ok(vec![2, 0, 1]));
+
+    let search = dfs(&dag, 2);
+    assert_eq!(search.pre_order(), &[2, 0, 1]);
+    assert_eq!(search.edge_kinds(2), &[EdgeKind::Tree, EdgeKind::Forward]);
+
+    let cyclic = Graph::from_directed_edges(2, vec![(0, 1, ()), (1, 0, ())]);
+    assert_eq!(topological_sort(&cyclic), Err(Cycle(vec![0, 1])));
+}
+
+#[test]
+fn strongly_connected_components_and_two_sat() {
+    let g = Graph::from_directed_edges(4, vec![(0, 1, ()), (1, 0, ()), (1, 2, ()), (2, 3, ())]);
+
+    let scc = strongly_connected_components(&g);
+    assert_eq!(scc.count(), 3);
+    assert!(scc.same(0, 1));
+    assert_eq!(scc.condensation(&g).edge_count(), 2);
+
+    // x0 || x1, !x0 || x1, !x1 || !x0
+    let mut problem = TwoSat::new(2);
+    problem.add_clause(0, true, 1, true);
+    problem.add_clause(0, false, 1, true);
+    problem.add_clause(1, false, 0, false);
+    assert_eq!(problem.solve(), Some(vec![false, true]));
+}
+
+#[test]
+fn minimum_spanning_tree() {
+    let g = Graph::from_undirected_edges(4, vec![(0, 1, 1), (1, 2, 2), (0, 2, 2), (2, 3, 5)]);
+
+    let forest = kruskal(&g).unwrap();
+    assert_eq!(forest.total_weight(), 8);
+    assert_eq!(forest.edges().len(), 3);
+    assert_eq!(prim(&g).unwrap().total_weight(), 8);
+}
@@ -0,0 +1,63 @@
+use algorithms_by_rust::others::{
+    OfflineDynamicConnectivity, RollbackUnionFind, UnionFind, WeightedUnionFind,
+};
+
+#[test]
+fn union_find_unite_and_same() {
+    let mut uf = UnionFind::new(4);
+
+    assert!(!uf.same(0, 3));
+    assert!(uf.unite(0, 1));
+    assert!(uf.unite(2, 3));
+    assert!(!uf.same(1, 2));
+    assert!(uf.unite(1, 3));
+    assert!(!uf.unite(0, 2));
+    assert!(uf.same(0, 2));
+    assert_eq!(uf.root(0), uf.root(3));
+}
+
+#[test]
+fn union_find_components() {
+    let mut uf = UnionFind::new(5);
+    uf.unite(0, 3);
+    uf.unite(3, 4);
+
+    assert_eq!(uf.size(4), 3);
+    assert_eq!(uf.count_components(), 3);
+    assert_eq!(uf.groups(), vec![vec![0, 3, 4], vec![1], vec![2]]);
+}
+
+#[test]
+fn weighted_union_find_diff() {
+    let mut uf = WeightedUnionFind::new(3);
+
+    assert_eq!(uf.unite(0, 1, 5), Ok(true));
+    assert_eq!(uf.unite(1, 2, -2), Ok(true));
+    assert_eq!(uf.diff(0, 2), Some(3));
+    assert!(uf.unite(2, 0, 0).is_err());
+}
+
+#[test]
+fn rollback_union_find() {
+    let mut uf = RollbackUnionFind::new(3);
+
+    let snapshot = uf.snapshot();
+    uf.unite(0, 1);
+    uf.unite(1, 2);
+    assert_eq!(uf.count_components(), 1);
+
+    uf.rollback(snapshot);
+    assert!(!uf.same(0, 1));
+}
+
+#[test]
+fn offline_dynamic_connectivity() {
+    let mut solver = OfflineDynamicConnectivity::new(3);
+    solver.add_edge(0, 1);
+    solver.add_edge(1, 2);
+    solver.connected(0, 2);
+    solver.remove_edge(0, 1);
+    solver.connected(0, 2);
+
+    assert_eq!(solver.solve(), vec![true, false]);
+}
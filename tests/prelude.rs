@@ -0,0 +1,15 @@
+use algorithms_by_rust::prelude::*;
+
+#[test]
+fn prelude_exports_items() {
+    let mut avl = AVL::new();
+    avl.add(1);
+    assert_eq!(avl.find(&1), Some(&1));
+
+    let mut uf = UnionFind::new(2);
+    uf.unite(0, 1);
+    assert!(uf.same(0, 1));
+
+    assert_eq!(merge_sort(vec![2, 1]), vec![1, 2]);
+    assert_eq!(binary_search(&[1, 2, 3], &3), Some(2));
+}
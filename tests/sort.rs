@@ -0,0 +1,22 @@
+use algorithms_by_rust::sort::{heap_sort, merge_sort, quick_sort};
+
+const SOURCE: [i32; 10] = [1, 4, 9, 7, 2, 3, 1, 10, 6, 8];
+const SORTED: [i32; 10] = [1, 1, 2, 3, 4, 6, 7, 8, 9, 10];
+
+#[test]
+fn heap_sort_numbers() {
+    assert_eq!(heap_sort(SOURCE.to_vec()), SORTED.to_vec());
+    assert_eq!(heap_sort(Vec::<i32>::new()), Vec::<i32>::new());
+}
+
+#[test]
+fn merge_sort_numbers() {
+    assert_eq!(merge_sort(SOURCE.to_vec()), SORTED.to_vec());
+    assert_eq!(merge_sort(Vec::<i32>::new()), Vec::<i32>::new());
+}
+
+#[test]
+fn quick_sort_numbers() {
+    assert_eq!(quick_sort(SOURCE.to_vec()), SORTED.to_vec());
+    assert_eq!(quick_sort(Vec::<i32>::new()), Vec::<i32>::new());
+}
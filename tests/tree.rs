@@ -0,0 +1,241 @@
+use std::collections::BTreeSet;
+
+use algorithms_by_rust::binary_tree;
+use algorithms_by_rust::tree::{AvlMap, BinaryTree, OrderedSet, RedBlackTree, AVL};
+
+const SOURCE: [i32; 9] = [7, 5, 4, 2, 6, 11, 9, 10, 13];
+
+#[test]
+fn binary_tree_find_and_remove() {
+    let mut tree = BinaryTree::make_tree(&SOURCE);
+
+    for n in SOURCE.iter() {
+        assert_eq!(tree.find(n), Some(n));
+    }
+    assert_eq!(tree.remove(&5), Some(5));
+    assert_eq!(tree.find(&5), None);
+    assert_eq!(tree.remove(&5), None);
+}
+
+#[test]
+fn binary_tree_macro() {
+    let tree = binary_tree![3, 1, 2];
+
+    assert_eq!(tree.find(&1), Some(&1));
+    assert_eq!(tree.find(&4), None);
+}
+
+#[test]
+fn avl_find_and_remove() {
+    let mut avl = AVL::from_slice(&SOURCE);
+
+    for n in SOURCE.iter() {
+        assert_eq!(avl.find(n), Some(n));
+    }
+    assert_eq!(avl.remove(&7), Some(7));
+    assert_eq!(avl.find(&7), None);
+
+    avl.add(7);
+    assert_eq!(avl.find(&7), Some(&7));
+}
+
+#[test]
+fn trees_of_strings() {
+    let words: Vec<String> = "the quick brown fox".split(' ').map(String::from).collect();
+    let avl: AVL<String> = words.iter().cloned().collect();
+    let tree = BinaryTree::make_tree(&words);
+
+    assert_eq!(avl.first().map(String::as_str), Some("brown"));
+    assert_eq!(tree.nth(3).map(String::as_str), Some("the"));
+
+    let balanced = AVL::from_sorted_iter(avl);
+    assert!(balanced.iter().eq(tree.iter()));
+    assert_eq!(BinaryTree::from_sorted_iter(tree).len(), words.len());
+}
+
+#[test]
+fn avl_ordered_iteration() {
+    let avl = AVL::from_slice(&SOURCE);
+
+    assert_eq!(avl.len(), SOURCE.len());
+    assert_eq!(avl.first(), Some(&2));
+    assert_eq!(avl.last(), Some(&13));
+    assert_eq!(
+        avl.iter().copied().collect::<Vec<_>>(),
+        vec![2, 4, 5, 6, 7, 9, 10, 11, 13]
+    );
+    assert_eq!(
+        avl.range(6..=10).copied().collect::<Vec<_>>(),
+        vec![6, 7, 9, 10]
+    );
+
+    let mut sum = 0;
+    for v in &avl {
+        sum += v;
+    }
+    assert_eq!(sum, SOURCE.iter().sum());
+    assert_eq!(avl.into_iter().next_back(), Some(13));
+}
+
+#[test]
+fn avl_order_statistics() {
+    let mut avl = AVL::new();
+    let mut medians = Vec::new();
+    for v in [5, 1, 9, 3, 7] {
+        avl.add(v);
+        medians.push(*avl.nth((avl.len() - 1) / 2).unwrap());
+    }
+
+    assert_eq!(medians, vec![5, 1, 5, 3, 5]);
+    assert_eq!(avl.rank(&7), 3);
+    assert_eq!(avl.rank(&6), 3);
+}
+
+#[test]
+fn avl_map_entry() {
+    let mut map = AvlMap::new();
+    for word in "the quick fox jumps over the lazy dog the end".split(' ') {
+        *map.entry(word).or_insert(0) += 1;
+    }
+
+    assert_eq!(map.get("the"), Some(&3));
+    assert_eq!(map.insert("fox", 10), Some(1));
+    assert_eq!(map.remove("dog"), Some(1));
+    assert_eq!(map.first_key_value(), Some((&"end", &1)));
+    assert_eq!(map.len(), 7);
+}
+
+#[test]
+fn multiset_trees() {
+    let mut avl = AVL::new_multiset();
+    let mut tree = BinaryTree::new_multiset();
+    for v in [3, 1, 3, 2, 3] {
+        avl.add(v);
+        tree.add(v);
+    }
+
+    assert_eq!(avl.count(&3), 3);
+    assert_eq!(tree.count(&3), 3);
+    assert_eq!(avl.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 3, 3]);
+
+    assert_eq!(avl.remove_one(&3), Some(3));
+    assert_eq!(tree.remove_one(&3), Some(3));
+    assert_eq!(avl.remove_all(&3), vec![3, 3]);
+    assert_eq!(tree.remove_all(&3), vec![3, 3]);
+    assert_eq!(avl.len(), 2);
+}
+
+#[test]
+fn avl_split_join_and_set_operations() {
+    let (lhs, rhs) = AVL::from_slice(&SOURCE).split(&9);
+    assert_eq!(lhs.iter().copied().collect::<Vec<_>>(), vec![2, 4, 5, 6, 7]);
+    assert_eq!(rhs.first(), Some(&9));
+
+    let mut avl = AVL::join(lhs, rhs);
+    assert_eq!(avl.len(), SOURCE.len());
+
+    avl.append(&mut AVL::from_slice(&[20, 30]));
+    assert_eq!(avl.last(), Some(&30));
+
+    let evens = AVL::from_slice(&[2, 4, 6, 8, 10]);
+    let smalls = AVL::from_slice(&[1, 2, 3, 4]);
+    assert_eq!(
+        evens.intersection(smalls).iter().copied().collect::<Vec<_>>(),
+        vec![2, 4]
+    );
+    assert_eq!(
+        AVL::from_slice(&[1, 3]).union(AVL::from_slice(&[2])).len(),
+        3
+    );
+    assert_eq!(
+        AVL::from_slice(&[1, 2, 3]).difference(AVL::from_slice(&[2])).iter().count(),
+        2
+    );
+}
+
+#[test]
+fn cursors() {
+    let avl = AVL::from_slice(&SOURCE);
+    let mut cursor = avl.lower_bound(&8);
+    assert_eq!(cursor.peek(), Some(&9));
+    assert_eq!(cursor.prev(), Some(&7));
+    assert_eq!(avl.upper_bound(&9).peek(), Some(&10));
+
+    let mut tree = BinaryTree::make_tree(&SOURCE);
+    let mut cursor = tree.lower_bound_mut(&5);
+    assert_eq!(cursor.remove_current(), Some(5));
+    assert_eq!(cursor.peek(), Some(&6));
+    assert_eq!(cursor.next(), Some(&7));
+    assert_eq!(tree.find(&5), None);
+}
+
+#[test]
+fn red_black_tree_find_and_remove() {
+    let mut tree = RedBlackTree::new();
+    for &n in SOURCE.iter() {
+        tree.add(n);
+    }
+
+    for n in SOURCE.iter() {
+        assert_eq!(tree.find(n), Some(n));
+    }
+    assert_eq!(tree.len(), SOURCE.len());
+    assert_eq!(tree.remove(&7), Some(7));
+    assert_eq!(tree.find(&7), None);
+    assert_eq!(tree.remove(&7), None);
+}
+
+#[test]
+fn rotation_counts() {
+    let mut avl = AVL::new();
+    let mut red_black_tree = RedBlackTree::new();
+    for n in 0..100 {
+        avl.add(n);
+        red_black_tree.add(n);
+    }
+
+    assert!(avl.rotation_count() > 0);
+    assert!(red_black_tree.rotation_count() > 0);
+}
+
+// Checks a set against BTreeSet, so that it is written once for every tree.
+fn check_ordered_set<S: OrderedSet<i32>>() {
+    let mut set: S = SOURCE.into_iter().collect();
+    let expected: BTreeSet<_> = SOURCE.into_iter().collect();
+
+    assert_eq!(set.len(), expected.len());
+    assert!(set.iter().eq(expected.iter()));
+    assert!(set.iter().rev().eq(expected.iter().rev()));
+    assert_eq!(set.iter().len(), expected.len());
+    assert_eq!(set.min(), expected.first());
+    assert_eq!(set.max(), expected.last());
+
+    assert!(!set.insert(7));
+    assert!(set.insert(8));
+    assert!(set.contains(&8));
+    assert_eq!(set.remove(&8), Some(8));
+    assert_eq!(set.remove(&8), None);
+    assert!(!set.contains(&8));
+
+    set.extend([1, 20]);
+    assert_eq!(set.min(), Some(&1));
+    assert_eq!(set.max(), Some(&20));
+    assert_eq!(set.len(), expected.len() + 2);
+
+    for n in SOURCE {
+        assert_eq!(set.remove(&n), Some(n));
+    }
+    assert!(set.into_iter().eq([1, 20]));
+
+    let empty = S::default();
+    assert!(empty.is_empty());
+    assert_eq!(empty.min(), None);
+    assert_eq!(empty.iter().next(), None);
+}
+
+#[test]
+fn ordered_sets() {
+    check_ordered_set::<BinaryTree<i32>>();
+    check_ordered_set::<AVL<i32>>();
+    check_ordered_set::<RedBlackTree<i32>>();
+}